@@ -1,28 +1,115 @@
 //--------------------------------------------------------------------
 // angle_key.rs
 //--------------------------------------------------------------------
-// A structure that contains the beginning angle of a curve, its
-// derivative and double derivative, used as a key to the DCEL
+// A structure that contains the beginning angle of a curve and its
+// first three derivatives, used as a key to the DCEL
 //--------------------------------------------------------------------
 
 use crate::geometry::*;
 use crate::ordered_float::OrderedFloat;
 
+// The third derivative breaks the ties between curves that meet with G2 continuity
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-pub struct AngleKey { t: OrderedFloat<Coord>, dt: OrderedFloat<Coord>, ddt: OrderedFloat<Coord> }
+pub struct AngleKey {
+    t: OrderedFloat<Coord>, dt: OrderedFloat<Coord>,
+    ddt: OrderedFloat<Coord>, dddt: OrderedFloat<Coord>
+}
 
 impl AngleKey {
-    pub fn new(t: Coord, dt: Coord, ddt: Coord) -> AngleKey {
-        let t = t.into();
+    // The angle is snapped to the epsilon grid, so curves that leave a point along the same tangent
+    // are ordered by their derivatives instead of by the rounding noise of their angles
+    pub fn new(t: Coord, dt: Coord, ddt: Coord, dddt: Coord) -> AngleKey {
+        let t = ((t / EPSILON).round() * EPSILON).into();
         let dt = dt.into();
         let ddt = ddt.into();
-        AngleKey { t, dt, ddt }
+        let dddt = dddt.into();
+        AngleKey { t, dt, ddt, dddt }
+    }
+
+    // Build the key from the curve's derivative p at its start point and the derivatives of p,
+    // taking the derivatives of the tangent angle with respect to the curve parameter
+    pub fn from_derivatives(p: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> AngleKey {
+        // The angle's derivative is p × p1 / |p|², so differentiate the quotient
+        let d = p.length_sq();
+        let d1 = 2.0 * p.dot(p1);
+        let d2 = 2.0 * (p1.dot(p1) + p.dot(p2));
+
+        let dt = p.cross(p1) / d;
+        let ddt = (p.cross(p2) - dt * d1) / d;
+        let dddt = (p1.cross(p2) + p.cross(p3) - 2.0 * ddt * d1 - dt * d2) / d;
+        AngleKey::new(p.angle(), dt, ddt, dddt)
     }
+
+    // The key used when all the curve's derivatives collapse at its start point,
+    // so the only direction left is the one of its chord
+    pub fn from_chord(a: Vec2, b: Vec2) -> AngleKey { AngleKey::new(a.angle_facing(b), 0.0, 0.0, 0.0) }
 }
 
 impl std::fmt::Debug for AngleKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({},{},{})", self.t, self.dt, self.ddt)
+        write!(f, "({},{},{},{})", self.t, self.dt, self.ddt, self.dddt)
     }
 }
 
+#[cfg(test)]
+mod test {
+    use crate::curve::*;
+
+    fn assert_well_defined(key: AngleKey) {
+        assert!(!key.t.is_nan() && !key.dt.is_nan() && !key.ddt.is_nan() && !key.dddt.is_nan(), "{:?}", key);
+    }
+
+    #[test]
+    fn test_collapsed_derivatives() {
+        let a = Vec2::new(1.0, 1.0);
+        let b = Vec2::new(1.0 + 1e-6, 1.0);
+        let c = Vec2::new(1.0, 1.0 + 1e-6);
+
+        assert_well_defined(Curve::quadratic_bezier(a, b, c).angle_key());
+        assert_well_defined(Curve::cubic_bezier(a, b, c, a).angle_key());
+        assert_well_defined(Curve::cubic_bezier(a, a, a, a).angle_key());
+
+        // A cubic with three coincident points still has a meaningful direction
+        let key = Curve::cubic_bezier(a, a, a, Vec2::new(1.0, 2.0)).angle_key();
+        assert_eq!(key, AngleKey::from_chord(a, Vec2::new(1.0, 2.0)));
+    }
+
+    fn assert_roughly_equal_keys(k1: AngleKey, k2: AngleKey) {
+        assert!(k1.t.roughly_equals(*k2.t) && k1.dt.roughly_equals(*k2.dt) && k1.ddt.roughly_equals(*k2.ddt)
+            && k1.dddt.roughly_equals(*k2.dddt), "{:?} != {:?}", k1, k2);
+    }
+
+    #[test]
+    fn test_elevated_curve_key() {
+        // The key depends only on the geometry and the parametrization, not on the curve degree
+        let quad = QuadraticBezier { a: Vec2::new(0.0, 0.0), b: Vec2::new(1.0, 2.0), c: Vec2::new(3.0, 1.0) };
+        assert_roughly_equal_keys(quad.angle_key(), quad.elevate().angle_key());
+    }
+
+    #[test]
+    fn test_third_derivative_tie_break() {
+        // Moving the last control point along the initial tangent keeps the
+        // angle and its first two derivatives, but changes the third one
+        let a = Vec2::new(0.0, 0.0);
+        let b = Vec2::new(1.0, 2.0);
+        let c = Vec2::new(3.0, 1.0);
+        let k1 = Curve::cubic_bezier(a, b, c, Vec2::new(4.0, 3.0)).angle_key();
+        let k2 = Curve::cubic_bezier(a, b, c, Vec2::new(4.0, 3.0) + (b - a) / 2.0).angle_key();
+
+        assert!(k1.t.roughly_equals(*k2.t) && k1.dt.roughly_equals(*k2.dt) && k1.ddt.roughly_equals(*k2.ddt));
+        assert!(!k1.dddt.roughly_equals(*k2.dddt));
+    }
+
+    #[test]
+    fn test_arc_key_matches_tangent() {
+        let arc = EllipticArc { center: Vec2::new(1.0, 1.0), radii: Vec2::new(2.0, 1.0),
+            crot: Vec2::from_angle(0.4), t1: 0.3, dt: -1.0 };
+        let key = arc.angle_key();
+        assert!(key.t.roughly_equals(arc.entry_tangent().angle()));
+
+        // Compare the derivative of the tangent angle with a finite difference
+        let h = 1e-6;
+        let dt = (Curve::EllipticArc(arc).tangent_at(h).angle() - arc.entry_tangent().angle()) / h;
+        assert!((*key.dt - dt).abs() < 1e-4, "{} != {}", key.dt, dt);
+    }
+}
\ No newline at end of file
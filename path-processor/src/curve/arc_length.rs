@@ -0,0 +1,146 @@
+//--------------------------------------------------------------------
+// arc_length.rs
+//--------------------------------------------------------------------
+// Provides the arc length of the curves and the mapping between
+// arc length and curve parameter
+//--------------------------------------------------------------------
+
+use crate::geometry::*;
+use super::*;
+
+// The nodes and weights of the 5-point Gauss-Legendre quadrature on [-1, 1]
+pub(super) const GAUSS_LEGENDRE: [(Coord, Coord); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1)
+];
+
+// The number of samples used when a length table is built implicitly
+pub const LENGTH_TABLE_SAMPLES: usize = 16;
+
+// Maximum number of Newton iterations used to invert the arc length
+const MAX_NEWTON_ITERATIONS: usize = 8;
+
+// Integrate the speed of a curve (given its derivative) between l and r
+fn integrate_speed(derivative: &Curve, l: Coord, r: Coord) -> Coord {
+    let half = (r - l) / 2.0;
+    let mid = (r + l) / 2.0;
+    GAUSS_LEGENDRE.iter().map(|&(x, w)| w * derivative.at(mid + half * x).length()).sum::<Coord>() * half
+}
+
+impl Curve {
+    pub fn length(&self) -> Coord { self.length_between(0.0, 1.0) }
+
+    pub fn length_between(&self, l: Coord, r: Coord) -> Coord {
+        match self {
+            // Lines have an exact formula
+            Curve::Line(ln) => (ln.b - ln.a).length() * (r - l),
+            _ => integrate_speed(&self.derivative(), l, r)
+        }
+    }
+
+    // Find the parameter where the arc length from the start of the curve is s
+    // For repeated queries on the same curve, prefer building a LengthTable
+    pub fn param_at_length(&self, s: Coord) -> Coord {
+        LengthTable::new(self, LENGTH_TABLE_SAMPLES).param_at_length(s)
+    }
+
+    pub fn at_length(&self, s: Coord) -> Vec2 { self.at(self.param_at_length(s)) }
+}
+
+// A precomputed table of cumulative arc lengths at evenly spaced parameters
+pub struct LengthTable {
+    derivative: Curve,
+    lengths: Vec<Coord>
+}
+
+impl LengthTable {
+    pub fn new(curve: &Curve, samples: usize) -> LengthTable {
+        let derivative = curve.derivative();
+        let samples = samples.max(1);
+
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut acc = 0.0;
+        lengths.push(acc);
+        for i in 0..samples {
+            let l = i as Coord / samples as Coord;
+            let r = (i+1) as Coord / samples as Coord;
+            acc += integrate_speed(&derivative, l, r);
+            lengths.push(acc);
+        }
+
+        LengthTable { derivative, lengths }
+    }
+
+    pub fn length(&self) -> Coord { self.lengths[self.lengths.len()-1] }
+
+    fn param_of_sample(&self, i: usize) -> Coord { i as Coord / (self.lengths.len() - 1) as Coord }
+
+    pub fn param_at_length(&self, s: Coord) -> Coord {
+        // Clamp the lengths outside the curve to the endpoints (zero-length curves also end here)
+        if s <= 0.0 || self.length().roughly_zero() { return 0.0; }
+        if s >= self.length() { return 1.0; }
+
+        // Find the sample interval that contains the length
+        let i = match self.lengths.binary_search_by(|l| l.partial_cmp(&s).unwrap()) {
+            Ok(i) => return self.param_of_sample(i),
+            Err(i) => i - 1
+        };
+
+        let tl = self.param_of_sample(i);
+        let tr = self.param_of_sample(i+1);
+
+        // Start from the linear interpolation and refine it with Newton iterations
+        let k = (s - self.lengths[i]) / (self.lengths[i+1] - self.lengths[i]);
+        let mut t = tl + k * (tr - tl);
+        for _ in 0..MAX_NEWTON_ITERATIONS {
+            let diff = self.lengths[i] + integrate_speed(&self.derivative, tl, t) - s;
+            let speed = self.derivative.at(t).length();
+            if diff.roughly_zero() || speed.roughly_zero() { break; }
+            t = (t - diff / speed).max(tl).min(tr);
+        }
+
+        t
+    }
+
+    pub fn at_length(&self, curve: &Curve, s: Coord) -> Vec2 { curve.at(self.param_at_length(s)) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_line_length() {
+        let line = Curve::line(Vec2::new(0.0, 0.0), Vec2::new(3.0, 4.0));
+        assert!(line.length().roughly_equals(5.0));
+        assert!(line.param_at_length(2.5).roughly_equals(0.5));
+        assert!(line.at_length(10.0).roughly_equals(Vec2::new(3.0, 4.0)));
+    }
+
+    #[test]
+    fn test_quadratic_length_inversion() {
+        let curve = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 4.0), Vec2::new(6.0, 0.0));
+        let table = LengthTable::new(&curve, LENGTH_TABLE_SAMPLES);
+
+        for i in 1..10 {
+            let s = table.length() * i as Coord / 10.0;
+            let t = table.param_at_length(s);
+
+            // Integrate the length up to t finely to compare it
+            let pieces = 64;
+            let length: Coord = (0..pieces).map(|k| {
+                curve.length_between(t * k as Coord / pieces as Coord, t * (k+1) as Coord / pieces as Coord)
+            }).sum();
+            assert!(length.roughly_equals(s), "Length mismatch at s = {}: got {}", s, length);
+        }
+    }
+
+    #[test]
+    fn test_zero_length_curve() {
+        let curve = Curve::cubic_bezier(Vec2::new(1.0, 1.0), Vec2::new(1.0, 1.0), Vec2::new(1.0, 1.0), Vec2::new(1.0, 1.0));
+        assert_eq!(curve.param_at_length(1.0), 0.0);
+    }
+}
@@ -13,8 +13,12 @@ use crate::arrayvec::*;
 use crate::vec_utils::*;
 use super::*;
 
+// The classification of a cubic according to its inflection points, as in the Loop-Blinn paper
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
+pub enum CubicKind { Serpentine, Loop, Cusp, Quadratic, Line }
+
 // The cubic bezier structure
-#[derive(Display)]
+#[derive(Copy, Clone, Display)]
 #[display(fmt = "CubicBezier({},{},{},{})", a, b, c, d)]
 pub struct CubicBezier { pub a: Vec2, pub b: Vec2, pub c: Vec2, pub d: Vec2 }
 
@@ -53,23 +57,127 @@ impl CubicBezier {
         CubicBezier { a: self.d, b: self.c, c: self.b, d: self.a }
     }
 
+    pub fn mirror(&self, axis_point: Vec2, axis_dir: Vec2) -> CubicBezier {
+        let r = |v: Vec2| v.reflect(axis_point, axis_dir);
+        CubicBezier { a: r(self.a), b: r(self.b), c: r(self.c), d: r(self.d) }
+    }
+
+    // Degree reduction, only possible if the cubic is an elevated quadratic
+    pub fn try_reduce(&self) -> Option<QuadraticBezier> {
+        if !(self.a - 3.0 * self.b + 3.0 * self.c - self.d).roughly_zero() { None }
+        else {
+            let b1 = 3.0 * self.b - self.a;
+            let b2 = 3.0 * self.c - self.d;
+            Some(QuadraticBezier { a: self.a, b: (b1 + b2) / 4.0, c: self.d })
+        }
+    }
+
+    // The coefficients (d1, d2, d3) of the homogeneous inflection point polynomial, computed
+    // from the canonical (power basis) form of the curve, following Chapter 4 of the Loop-Blinn paper
+    pub fn inflection_coefficients(&self) -> (Coord, Coord, Coord) {
+        let c3 = -self.a + 3.0 * self.b - 3.0 * self.c + self.d;
+        let c2 = 3.0 * self.a - 6.0 * self.b + 3.0 * self.c;
+        let c1 = -3.0 * self.a + 3.0 * self.b;
+        (c2.cross(c3), c3.cross(c1), c1.cross(c2))
+    }
+
+    // The parameters in [0, 1] where the curvature changes sign, in increasing order
+    pub fn inflection_points(&self) -> ArrayVec<[Coord; 2]> {
+        // The cross product of the first and second derivatives is 6d1 t² - 6d2 t + 2d3
+        let (d1, d2, d3) = self.inflection_coefficients();
+        let mut v: ArrayVec<[Coord; 2]> = roots::find_roots_quadratic(3.0 * d1, -3.0 * d2, d3)
+            .as_ref().iter().copied().filter(|&t| inside01(t)).collect();
+        v.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        v
+    }
+
+    // The parameters where the curve's extension meets itself, if the cubic is a loop; they
+    // may lie outside [0, 1]. Uses the method based on https://stackoverflow.com/a/38644407
+    pub fn loop_parameters(&self) -> ArrayVec<[Coord; 2]> {
+        let mut da = self.b - self.a;
+        let mut db = self.c - self.b;
+        let mut dc = self.d - self.c;
+
+        // Check for no loops on cross product
+        let ab = da.cross(db);
+        let ac = da.cross(dc);
+        let bc = db.cross(dc);
+        if ac * ac > 4.0 * ab * bc { return ArrayVec::new(); }
+
+        // The coefficients of the canonical form
+        let mut c3 = da + dc - 2.0 * db;
+
+        // Rotate if beforehand if necessary
+        if !c3.y.roughly_zero() {
+            c3 = Vec2::new(c3.x, -c3.y);
+            da = da.rot_scale(c3);
+            db = db.rot_scale(c3);
+            dc = dc.rot_scale(c3);
+            c3 = da + dc - 2.0 * db;
+        }
+
+        let c2 = 3.0 * (db - da);
+        let c1 = 3.0 * da;
+
+        // Calculate the coefficients of the loop polynomial
+        let bb = -c1.y / c2.y;
+        let s1 = c1.x / c3.x;
+        let s2 = c2.x / c3.x;
+
+        // Find the roots (that happen to be the loop points)
+        roots::find_roots_quadratic(1.0, -bb, bb * (bb + s2) + s1).as_ref().iter().copied().collect()
+    }
+
+    // The pair of parameters in [0, 1] where the curve crosses itself, in increasing order; a curve
+    // that only closes on itself (like the loop cut at its double point) doesn't count
+    pub fn self_intersection(&self) -> Option<(Coord, Coord)> {
+        match self.loop_parameters().as_slice() {
+            &[t1, t2] if inside01(t1) && inside01(t2) && t1 != t2 => {
+                let (t1, t2) = (t1.min(t2), t1.max(t2));
+                if t1.roughly_zero() && (1.0 - t2).roughly_zero() { None } else { Some((t1, t2)) }
+            }
+            _ => None
+        }
+    }
+
+    pub fn classify(&self) -> CubicKind {
+        let (d1, d2, d3) = self.inflection_coefficients();
+
+        // The coefficients grow with the square of the curve's size, so compare them relative to it
+        let size = [self.b, self.c, self.d].iter().map(|&v| (v - self.a).length_sq()).fold(0.0, Coord::max);
+        if size.roughly_zero_squared() { return CubicKind::Line; }
+        let zero = |d: Coord| (d / size).roughly_zero_squared();
+
+        if !zero(d1) {
+            let disc = 3.0 * d2 * d2 - 4.0 * d3 * d1;
+            if (disc / (size * size)).roughly_zero_squared() { CubicKind::Cusp }
+            else if disc > 0.0 { CubicKind::Serpentine }
+            else { CubicKind::Loop }
+        }
+        // A cusp with an inflection point at infinity
+        else if !zero(d2) { CubicKind::Cusp }
+        else if !zero(d3) { CubicKind::Quadratic }
+        else { CubicKind::Line }
+    }
+
     pub fn winding(&self) -> Coord { 
         (6.0 * self.a.cross(self.b) + 3.0 * self.a.cross(self.c) + self.a.cross(self.d) +
             3.0 * self.b.cross(self.c) + 3.0 * self.b.cross(self.d) + 6.0 * self.c.cross(self.d)) / 10.0
     }
 
+    #[cfg(feature = "std")]
     pub fn angle_key(&self) -> AngleKey {
         let dv1 = self.b - self.a;
         let dv2 = self.c - self.b;
         let dv3 = self.d - self.c;
 
         // If dv1 is zero, the following angles will fall apart, so we take the limit
-        if dv1.roughly_zero() { self.derivative().angle_key() }
-        else {
-            let dt = 2.0 * dv1.cross(dv2 - dv1) / dv1.length_sq();
-            let ddt = (2.0 * dv1.cross(dv3 - 2.0 * dv2 + dv1) - 8.0 * dv1.dot(dv2 - dv1) * dt) / dv1.length_sq();
-            AngleKey::new(dv1.angle(), dt, ddt)
+        // (and if the whole curve collapses, there is no limit to take, so use the chord)
+        if dv1.roughly_zero() {
+            if dv2.roughly_zero() && dv3.roughly_zero() { AngleKey::from_chord(self.a, self.d) }
+            else { self.derivative().angle_key() }
         }
+        else { AngleKey::from_derivatives(dv1, 2.0 * (dv2 - dv1), 2.0 * (dv3 - 2.0 * dv2 + dv1), Vec2::zero()) }
     }
 
     pub fn intersection_x(&self, x: Coord) -> roots::Roots<Coord> {
@@ -103,15 +211,24 @@ impl CubicBezier {
         [self.a, self.b, self.c, self.d].into_iter().copied().collect()
     }
 
-    pub fn critical_points(&self) -> ArrayVec<[Coord; MAX_CRITICAL_POINTS]> {
+    // The exact bounding box, from the roots of the derivative on each coordinate and the endpoints
+    pub fn tight_bbox(&self) -> Rect {
+        let extrema = self.extrema_params().into_iter().map(|t| self.at(t));
+        Rect::enclosing_rect([self.a, self.d].iter().copied().chain(extrema)).unwrap()
+    }
+
+    // The parameters strictly between 0 and 1 where either coordinate has an extremum, unsorted
+    fn extrema_params(&self) -> ArrayVec<[Coord; 4]> {
         let dd = self.derivative();
         let tx = roots::find_roots_quadratic(dd.a.x - 2.0 * dd.b.x + dd.c.x, 2.0 * (dd.b.x - dd.a.x), dd.a.x);
         let ty = roots::find_roots_quadratic(dd.a.y - 2.0 * dd.b.y + dd.c.y, 2.0 * (dd.b.y - dd.a.y), dd.a.y);
+        tx.as_ref().iter().chain(ty.as_ref()).copied().filter(|&t| t > 0.0 && t < 1.0).collect()
+    }
 
+    pub fn critical_points(&self) -> ArrayVec<[Coord; MAX_CRITICAL_POINTS]> {
         let mut v = ArrayVec::new();
         v.push(0.0);
-        v.extend(tx.as_ref().iter().copied());
-        v.extend(ty.as_ref().iter().copied());
+        v.extend(self.extrema_params());
         v.push(1.0);
         v.retain(|&mut t| inside01(t));
         v.sort_unstable_by(|a: &Coord, b: &Coord| a.partial_cmp(b).unwrap());
@@ -119,3 +236,61 @@ impl CubicBezier {
         v
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_self_intersection() {
+        let looping = CubicBezier { a: Vec2::new(0.0, 0.0), b: Vec2::new(4.0, 1.0), c: Vec2::new(-1.0, 1.0), d: Vec2::new(3.0, 0.0) };
+        let (t1, t2) = looping.self_intersection().unwrap();
+        assert!(t1 < t2);
+        assert!((looping.at(t1) - looping.at(t2)).length() < 1e-6);
+
+        // A serpentine doesn't cross itself, and neither does a loop cut before its double point
+        let serpentine = CubicBezier { a: Vec2::new(0.0, 0.0), b: Vec2::new(1.0, 2.0), c: Vec2::new(3.0, -2.0), d: Vec2::new(4.0, 0.5) };
+        assert!(serpentine.self_intersection().is_none());
+        assert!(looping.subcurve(0.0, (t1 + t2) / 2.0).self_intersection().is_none());
+    }
+
+    fn cubic(a: (Coord, Coord), b: (Coord, Coord), c: (Coord, Coord), d: (Coord, Coord)) -> CubicBezier {
+        CubicBezier { a: Vec2::new(a.0, a.1), b: Vec2::new(b.0, b.1), c: Vec2::new(c.0, c.1), d: Vec2::new(d.0, d.1) }
+    }
+
+    #[test]
+    fn test_classify() {
+        let serpentine = cubic((0.0, 0.0), (1.0, 1.0), (2.0, -1.0), (4.0, 0.0));
+        assert_eq!(serpentine.classify(), CubicKind::Serpentine);
+        let points = serpentine.inflection_points();
+        assert_eq!(points.len(), 1);
+        let dd = serpentine.derivative();
+        assert!(dd.at(points[0]).cross(dd.derivative().at(points[0])).abs() < 1e-9);
+
+        // A symmetric S curve has its second inflection point at infinity, which the paper calls a cusp
+        assert_eq!(cubic((0.0, 0.0), (1.0, 1.0), (2.0, -1.0), (3.0, 0.0)).classify(), CubicKind::Cusp);
+        assert_eq!(cubic((0.0, 0.0), (4.0, 1.0), (-1.0, 1.0), (3.0, 0.0)).classify(), CubicKind::Loop);
+        assert_eq!(cubic((0.0, 0.0), (2.0, 1.0), (0.0, 1.0), (2.0, 0.0)).classify(), CubicKind::Cusp);
+
+        // An elevated quadratic and a straight cubic
+        let quadratic = cubic((0.0, 0.0), (2.0 / 3.0, 4.0 / 3.0), (4.0 / 3.0, 4.0 / 3.0), (2.0, 0.0));
+        assert_eq!(quadratic.classify(), CubicKind::Quadratic);
+        assert!(quadratic.inflection_points().is_empty());
+        assert_eq!(cubic((0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)).classify(), CubicKind::Line);
+    }
+
+    #[test]
+    fn test_elevate_and_reduce() {
+        // Elevating a quadratic and reducing it back gives the same curve, ending where the cubic does
+        let quadratic = QuadraticBezier { a: Vec2::new(0.0, 0.0), b: Vec2::new(1.0, 3.0), c: Vec2::new(4.0, 1.0) };
+        let elevated = quadratic.elevate();
+        for &t in &[0.0, 0.25, 0.5, 0.75, 1.0] { assert!(elevated.at(t).roughly_equals(quadratic.at(t))); }
+
+        let reduced = elevated.try_reduce().unwrap();
+        assert!(reduced.a.roughly_equals(quadratic.a) && reduced.b.roughly_equals(quadratic.b));
+        assert!(reduced.c == elevated.d);
+
+        // A genuine cubic can't be reduced
+        assert!(cubic((0.0, 0.0), (1.0, 1.0), (2.0, -1.0), (4.0, 0.0)).try_reduce().is_none());
+    }
+}
@@ -12,7 +12,14 @@ use crate::arrayvec::*;
 use super::*;
 use crate::vec_utils::*;
 
+// The maximum number of cubics an arc is split into, guarding against tiny tolerances
+const MAX_ARC_CUBICS: usize = 64;
+
+// The extrema tight_bbox takes: the minimum and the maximum of each coordinate
+const MAX_ARC_EXTREMA: usize = 4;
+
 // The elliptic arc structure
+// The parameter functions assume |dt| < 2π; larger arcs must be split with split_full_turns
 #[derive(Copy, Clone, Display)]
 #[display(fmt = "EllipticArc(center = {}, radii = {}, rotation = {}, t1 = {}, dt = {})",
     center, radii, "crot.angle().to_degrees()", "t1.to_degrees()", "dt.to_degrees()")]
@@ -21,27 +28,20 @@ pub struct EllipticArc { pub center: Vec2, pub radii: Vec2, pub crot: Vec2, pub
 impl EllipticArc {
     pub fn local_to_global(&self, p: Vec2) -> Vec2 { self.center + self.crot.rot_scale(p) }
     fn delta_at(&self, t: Coord) -> Vec2 {
-        let th = self.t1 * t + self.dt;
+        let th = self.t1 + self.dt * t;
         Vec2::new(self.radii.x * th.cos(), self.radii.y * th.sin())
     }
 
     pub fn lesser_angle(&self) -> Coord { self.t1.min(self.t1 + self.dt) }
     pub fn greater_angle(&self) -> Coord { self.t1.max(self.t1 + self.dt) }
 
+    // The parameter of the first angle of the sweep equivalent to theta, or INFINITY if there is none;
+    // the equivalent angle is found directly, so the arcs far away from the first turn are handled
+    // too, and the parameter is clamped so rounding can't push it out of [0,1]
     pub fn angle_to_param(&self, theta: Coord) -> Coord {
-        let theta = theta.wrap_angle();
-
-        // Test the angle and up to two double turns before and after
-        let mut i = -2.0;
-        while i <= 2.0 {
-            let cand = theta + i * 2.0 * PI;
-            if self.lesser_angle() <= cand && cand <= self.greater_angle() {
-                return (cand - self.t1) / self.dt;
-            }
-            i += 1.0;
-        }
-
-        INFINITY
+        let cand = theta + 2.0 * PI * ((self.lesser_angle() - theta) / (2.0 * PI)).ceil();
+        if cand <= self.greater_angle() { ((cand - self.t1) / self.dt).clamp(0.0, 1.0) }
+        else { INFINITY }
     }
 
     pub fn at(&self, t: Coord) -> Vec2 { self.local_to_global(self.delta_at(t)) }
@@ -63,6 +63,71 @@ impl EllipticArc {
         EllipticArc { t1: self.t1 + self.dt, dt: -self.dt, ..*self }
     }
 
+    // Mirroring the ellipse's axes reverses its orientation, so the angles are negated
+    // in order to keep each parameter mapped to the reflection of its original point
+    pub fn mirror(&self, axis_point: Vec2, axis_dir: Vec2) -> EllipticArc {
+        let center = self.center.reflect(axis_point, axis_dir);
+        let crot = self.crot.reflect(Vec2::zero(), axis_dir);
+        EllipticArc { center, radii: self.radii, crot, t1: -self.t1, dt: -self.dt }
+    }
+
+    // Split the arc in equal pieces that sweep less than a full turn each; the arcs of whole
+    // turns are split in half turns, so a full circle becomes two half circles and the repeated
+    // turns of a longer arc become duplicates of them
+    pub fn split_full_turns(&self) -> impl Iterator<Item = EllipticArc> {
+        let turns = self.dt.abs() / (2.0 * PI);
+        let n = if turns < 1.0 && !turns.roughly_equals(1.0) { 1 }
+            else if (2.0 * turns).roughly_equals((2.0 * turns).round()) { (2.0 * turns).round() as usize }
+            else { turns.round() as usize + 1 };
+
+        let (arc, dt) = (*self, self.dt / n as Coord);
+        (0..n).map(move |i| EllipticArc { t1: arc.t1 + i as Coord * dt, dt, ..arc })
+    }
+
+    // Approximate the arc by cubics, splitting it in equal pieces of at most a quarter turn
+    // until the radial error of the usual 4/3 tan(θ/4) construction is within tolerance
+    pub fn to_cubics(&self, tolerance: Coord) -> Vec<CubicBezier> {
+        let radius = self.radii.x.max(self.radii.y);
+        let error = |dt: Coord| {
+            let s = (dt / 4.0).sin();
+            radius * 2.0 / 27.0 * s.powi(6) / (dt / 4.0).cos().powi(2)
+        };
+
+        let mut n = ((self.dt.abs() / FRAC_PI_2).ceil() as usize).max(1);
+        while n < MAX_ARC_CUBICS && error(self.dt.abs() / n as Coord) > tolerance { n += 1; }
+
+        let dt = self.dt / n as Coord;
+        let k = 4.0 / 3.0 * (dt / 4.0).tan();
+        let tangent = |th: Coord| self.crot.rot_scale(Vec2::new(-self.radii.x * th.sin(), self.radii.y * th.cos()));
+
+        (0..n).map(|i| {
+            let (th0, th1) = (self.t1 + i as Coord * dt, self.t1 + (i + 1) as Coord * dt);
+            let a = self.at(i as Coord / n as Coord);
+            let d = self.at((i + 1) as Coord / n as Coord);
+            CubicBezier { a, b: a + k * tangent(th0), c: d - k * tangent(th1), d }
+        }).collect()
+    }
+
+    // The exact bounding box: each coordinate has its extrema every half turn from the angles
+    // below, alternating between its minimum and its maximum, so the first two of them that the arc
+    // sweeps are enough, even if it sweeps more than a turn; the endpoints are taken too
+    pub fn tight_bbox(&self) -> Rect {
+        let ax = (-self.radii.y * self.crot.y).atan2(self.radii.x * self.crot.x);
+        let ay = (self.radii.y * self.crot.x).atan2(self.radii.x * self.crot.y);
+        let (lesser, greater) = (self.lesser_angle(), self.greater_angle());
+
+        let mut points = ArrayVec::<[Vec2; 2 + MAX_ARC_EXTREMA]>::new();
+        points.push(self.at(0.0));
+        points.push(self.at(1.0));
+        for &a in &[ax, ay] {
+            let k = ((lesser - a) / PI).ceil();
+            for th in [k, k + 1.0].iter().map(|k| a + k * PI).take_while(|&th| th <= greater) {
+                points.push(self.local_to_global(Vec2::new(self.radii.x * th.cos(), self.radii.y * th.sin())));
+            }
+        }
+        Rect::enclosing_rect(points.into_iter()).unwrap()
+    }
+
     pub fn critical_points(&self) -> ArrayVec<[Coord; MAX_CRITICAL_POINTS]> {
         let ax = (-self.radii.y * self.crot.y).atan2(self.radii.x * self.crot.x);
         let ay = (self.radii.y * self.crot.x).atan2(self.radii.x * self.crot.y);
@@ -86,52 +151,47 @@ impl EllipticArc {
         self.dt * self.radii.x * self.radii.y + self.center.cross(self.crot.rot_scale(p1 - p0))
     }
 
+    #[cfg(feature = "std")]
     pub fn angle_key(&self) -> AngleKey {
-        let pr = self.delta_at(0.0);
-        let r = self.radii;
+        // The derivatives of an arc are arcs too
+        let d1 = self.derivative();
+        let d2 = d1.derivative();
+        let d3 = d2.derivative();
+        let d4 = d3.derivative();
+        AngleKey::from_derivatives(d1.at(0.0), d2.at(0.0), d3.at(0.0), d4.at(0.0))
+    }
 
-        let dts = (1.0 as Coord).copysign(self.dt); // basically 1 * the sign of dt
-        let t = (self.crot.angle() + (dts * pr.y).atan2(-dts * pr.x)).wrap_angle();
-        let dt = -self.dt * r.x * r.y / pr.length_sq();
-        let ddt = 2.0 * dt * (2.0 * self.t1).sin() * (r.x * r.x - r.y * r.y);
-        AngleKey::new(t, dt, ddt)
+    // The arc meets a line on the angles base ± acos(ratio); return only the ones that lie on the arc
+    fn angle_roots(&self, ratio: Coord, base: Coord) -> roots::Roots<Coord> {
+        // If the difference is too large (or the ratio is undefined), bail out
+        if ratio.is_nan() || ratio.abs() > 1.0 { return roots::Roots::No([]); }
+
+        let acos = ratio.acos();
+        [base + acos, base - acos].iter().map(|&th| self.angle_to_param(th))
+            .filter(|t| t.is_finite())
+            .fold(roots::Roots::No([]), |roots, t| roots.add_new_root(t))
     }
 
     pub fn intersection_x(&self, x: Coord) -> roots::Roots<Coord> {
         // Get the compensation vector and the difference
-        let cp = Vec2::new(self.radii.x * self.crot.x, self.radii.y * self.crot.y);
+        let cp = Vec2::new(self.radii.x * self.crot.x, -self.radii.y * self.crot.y);
         let diff = x - self.center.x;
-        // If the difference is two large, bail out
-        if diff.abs() > cp.length() { roots::Roots::No([]) }
-        else {
-            let acos = (diff / cp.length()).acos();
-            roots::Roots::Two([self.angle_to_param(acos + cp.angle()), self.angle_to_param(-acos + cp.angle())])
-        }
+        self.angle_roots(diff / cp.length(), cp.angle())
     }
 
     pub fn intersection_y(&self, y: Coord) -> roots::Roots<Coord> {
         // Get the compensation vector and the difference
         let cp = Vec2::new(self.radii.x * self.crot.y, self.radii.y * self.crot.x);
         let diff = y - self.center.y;
-        // If the difference is two large, bail out
-        if diff.abs() > cp.length() { roots::Roots::No([]) }
-        else {
-            let acos = (diff / cp.length()).acos();
-            roots::Roots::Two([self.angle_to_param(acos + cp.angle()), self.angle_to_param(-acos + cp.angle())])
-        }
+        self.angle_roots(diff / cp.length(), cp.angle())
     }
 
     pub fn intersection_seg(&self, v1: Vec2, v2: Vec2) -> roots::Roots<Coord> {
-        // Get the compensation vector and the difference
+        // Get the compensation vector and the difference (the line's direction is brought to local space)
         let dv = v2 - v1;
-        let cp = Vec2::new(self.radii.x * self.crot.cross(dv), self.radii.y * self.crot.dot(dv));
-        let diff = (self.center - v1).cross(dv);
-        // If the difference is two large, bail out
-        if diff.abs() > cp.length() { roots::Roots::No([]) }
-        else {
-            let acos = (diff / cp.length()).acos();
-            roots::Roots::Two([self.angle_to_param(acos + cp.angle()), self.angle_to_param(-acos + cp.angle())])
-        }
+        let cp = Vec2::new(self.radii.x * self.crot.cross(dv), -self.radii.y * self.crot.dot(dv));
+        let diff = (v1 - self.center).cross(dv);
+        self.angle_roots(diff / cp.length(), cp.angle())
     }
 
     pub fn entry_tangent(&self) -> Vec2 { self.derivative().at(0.0).normalized() }
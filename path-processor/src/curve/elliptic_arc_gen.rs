@@ -8,7 +8,24 @@ use crate::geometry::*;
 use super::elliptic_arc::EllipticArc;
 use CoordM::consts::PI;
 
-pub fn from_path_params(cur: Vec2, mut radii: Vec2, xrot: Coord, large_arc: bool, sweep: bool, target: Vec2) -> EllipticArc {
+// The arc generated from the SVG parameters, telling whether the radii had to be scaled up to
+// reach the target (in which case the arc's radii differ from the ones given)
+#[derive(Copy, Clone)]
+pub struct PathParamsArc { pub arc: EllipticArc, pub radii_were_scaled: bool }
+
+pub fn from_path_params(cur: Vec2, radii: Vec2, xrot: Coord, large_arc: bool, sweep: bool, target: Vec2) -> EllipticArc {
+    from_path_params_reporting(cur, radii, xrot, large_arc, sweep, target).arc
+}
+
+pub fn from_path_params_reporting(cur: Vec2, mut radii: Vec2, xrot: Coord, large_arc: bool, sweep: bool,
+    target: Vec2) -> PathParamsArc {
+    // The spec leaves arcs between coincident endpoints undefined, and the center computation
+    // would divide by zero, so make it a point, which the simplification then drops
+    if cur.roughly_equals(target) {
+        let arc = EllipticArc { center: cur, radii: Vec2::zero(), crot: Vec2::from_angle(xrot), t1: 0.0, dt: 0.0 };
+        return PathParamsArc { arc, radii_were_scaled: false };
+    }
+
     // The algorithm used here is presented on this link: https://svgwg.org/svg2-draft/implnote.html
     let xpun = (cur - target) / 2.0;
     let xpr = xpun.rotate_by_angle(-xrot);
@@ -20,7 +37,8 @@ pub fn from_path_params(cur: Vec2, mut radii: Vec2, xrot: Coord, large_arc: bool
     // Guarantee that the radii are large enough
     let rr = radii.x * radii.x * xpr.y * xpr.y + radii.y * radii.y * xpr.x * xpr.x;
     let r2 = radii.x * radii.x * radii.y * radii.y;
-    let skr = if rr > r2 { radii *= (rr/r2).sqrt(); 0.0 } else { ((r2 - rr) / rr).sqrt() };
+    let radii_were_scaled = rr > r2;
+    let skr = if radii_were_scaled { radii *= (rr/r2).sqrt(); 0.0 } else { ((r2 - rr) / rr).sqrt() };
 
     // Calculate the rotated and unrotated center
     let mut cpr = Vec2::new(skr * radii.x * xpr.y / radii.y, -skr * radii.y * xpr.x / radii.x);
@@ -35,10 +53,57 @@ pub fn from_path_params(cur: Vec2, mut radii: Vec2, xrot: Coord, large_arc: bool
     if !sweep && dt > 0.0 { dt -= 2.0 * PI; }
     else if sweep && dt < 0.0 { dt += 2.0 * PI; }
 
-    EllipticArc { center: cpun, radii, crot: Vec2::from_angle(xrot), t1, dt }
+    let arc = EllipticArc { center: cpun, radii, crot: Vec2::from_angle(xrot), t1, dt };
+    PathParamsArc { arc, radii_were_scaled }
 }
 
+// The arcs must sweep less than a full turn, so equal start and end directions give an empty arc;
+// full_circle gives the two halves of a full turn instead
 pub fn circle(center: Vec2, radius: Coord, v1: Vec2, v2: Vec2, ccw: bool) -> EllipticArc {
     EllipticArc { center, radii: Vec2::new(radius, radius), crot: v1.normalized(),
         t1: 0.0, dt: v1.angle_between(v2).wrap_angle_360(ccw) }
 }
+
+pub fn full_circle(center: Vec2, radius: Coord, start: Vec2, ccw: bool) -> [EllipticArc; 2] {
+    [circle(center, radius, start, -start, ccw), circle(center, radius, -start, start, ccw)]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_radii_scaling_is_reported() {
+        let (cur, target) = (Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+
+        let result = from_path_params_reporting(cur, Vec2::new(3.0, 3.0), 0.0, false, true, target);
+        assert!(!result.radii_were_scaled);
+        assert!(result.arc.radii.roughly_equals(Vec2::new(3.0, 3.0)));
+
+        // Radii too small to reach the target are scaled so the center lies between the endpoints
+        let result = from_path_params_reporting(cur, Vec2::new(1.0, 1.0), 0.0, false, true, target);
+        assert!(result.radii_were_scaled);
+        assert!(result.arc.radii.roughly_equals(Vec2::new(2.0, 2.0)));
+        assert!(result.arc.center.roughly_equals(Vec2::new(2.0, 0.0)));
+    }
+
+    #[test]
+    fn test_full_circle() {
+        for &ccw in &[false, true] {
+            let [a, b] = full_circle(Vec2::new(1.0, 2.0), 3.0, Vec2::new(0.0, 1.0), ccw);
+            assert!(a.dt.abs().roughly_equals(PI) && b.dt.abs().roughly_equals(PI));
+            assert_eq!(a.dt < 0.0, ccw);
+            assert_eq!(b.dt < 0.0, ccw);
+            assert!(a.at(0.0).roughly_equals(Vec2::new(1.0, 5.0)) && a.at(1.0).roughly_equals(b.at(0.0)));
+            assert!(b.at(1.0).roughly_equals(a.at(0.0)));
+        }
+    }
+
+    #[test]
+    fn test_coincident_endpoints() {
+        let p = Vec2::new(10.0, 10.0);
+        let arc = from_path_params(p, Vec2::new(5.0, 5.0), 0.0, false, false, p);
+        assert!(arc.center.is_finite() && arc.radii.is_finite() && arc.t1.is_finite() && arc.dt.is_finite());
+        assert!(arc.at(0.0).roughly_equals(p) && arc.at(0.5).roughly_equals(p) && arc.at(1.0).roughly_equals(p));
+    }
+}
@@ -0,0 +1,78 @@
+//--------------------------------------------------------------------
+// flatten.rs
+//--------------------------------------------------------------------
+// Provides the approximation of the curves by polylines
+//--------------------------------------------------------------------
+
+use crate::geometry::*;
+use super::*;
+
+// The maximum number of times a piece of the curve is split, guarding against tiny tolerances
+const MAX_FLATTEN_DEPTH: usize = 16;
+
+impl Curve {
+    // The points of a polyline that stays within the tolerance of the curve, including both
+    // endpoints; the curve is first split on its monotone spans, so no piece can turn back
+    pub fn flatten(&self, tolerance: Coord) -> Vec<Vec2> {
+        let mut points = vec![self.at(0.0)];
+        if !self.is_line() {
+            for (l, r) in self.monotone_spans() {
+                flatten_piece(self, &mut points, l, r, tolerance, 0);
+            }
+        }
+
+        points.push(self.at(1.0));
+        points
+    }
+}
+
+// Push the points of the piece between l and r, except the first one
+fn flatten_piece(curve: &Curve, out: &mut Vec<Vec2>, l: Coord, r: Coord, tolerance: Coord, depth: usize) {
+    let (a, b) = (curve.at(l), curve.at(r));
+    let error = [0.25, 0.5, 0.75].iter()
+        .map(|&s| distance_to_segment(curve.at(l + s * (r - l)), a, b))
+        .fold(0.0, Coord::max);
+
+    if error > tolerance && depth < MAX_FLATTEN_DEPTH {
+        let m = (l + r) / 2.0;
+        flatten_piece(curve, out, l, m, tolerance, depth + 1);
+        flatten_piece(curve, out, m, r, tolerance, depth + 1);
+    } else if r < 1.0 { out.push(b); }
+}
+
+fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> Coord {
+    let d = b - a;
+    if d.roughly_zero() { return (p - a).length(); }
+    let t = ((p - a).dot(d) / d.length_sq()).clamp(0.0, 1.0);
+    (p - (a + t * d)).length()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_flatten() {
+        let line = Curve::line(Vec2::new(0.0, 0.0), Vec2::new(2.0, 1.0));
+        assert_eq!(line.flatten(1e-3).len(), 2);
+
+        let curves = [
+            Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0), Vec2::new(4.0, 0.0)),
+            Curve::cubic_bezier(Vec2::new(0.0, 0.0), Vec2::new(4.0, 1.0), Vec2::new(-1.0, 1.0), Vec2::new(3.0, 0.0)),
+            Curve::circle(Vec2::zero(), 2.0, Vec2::new(1.0, 0.0), Vec2::new(-1.0, 0.0), false)
+        ];
+
+        for curve in curves.iter() {
+            let coarse = curve.flatten(1e-1);
+            let fine = curve.flatten(1e-3);
+            assert!(coarse.len() < fine.len());
+            assert!(fine[0].roughly_equals(curve.at(0.0)) && fine[fine.len()-1].roughly_equals(curve.at(1.0)));
+
+            // Every sample of the curve must be close to the polyline
+            for (_, p, _) in curve.samples(257) {
+                let dist = fine.windows(2).map(|w| distance_to_segment(p, w[0], w[1])).fold(Coord::INFINITY, Coord::min);
+                assert!(dist <= 2e-3, "{}", curve);
+            }
+        }
+    }
+}
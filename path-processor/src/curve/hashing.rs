@@ -0,0 +1,80 @@
+//--------------------------------------------------------------------
+// hashing.rs
+//--------------------------------------------------------------------
+// Provides the hashing and equality of curves, quantizing their
+// coordinates so they can key caches of intersection results
+//--------------------------------------------------------------------
+
+use crate::geometry::*;
+use crate::arrayvec::ArrayVec;
+use super::*;
+use core::hash::{Hash, Hasher};
+
+// The grid the coordinates are snapped to before being compared or hashed
+const HASH_GRID: Coord = EPSILON;
+
+type CurveKey = (u8, ArrayVec<[i64; 11]>);
+
+impl Curve {
+    // The kind of the curve along with its quantized coordinates; arcs are keyed by their
+    // endpoints, center, radii, rotation and sweep, since their starting angle can wrap around
+    fn quantized_key(&self) -> CurveKey {
+        let mut key = ArrayVec::new();
+        let mut push = |v: Vec2| {
+            let q = v.snap(HASH_GRID) / HASH_GRID;
+            key.push(q.x as i64);
+            key.push(q.y as i64);
+        };
+
+        let kind = match self {
+            Curve::Line(l) => { push(l.a); push(l.b); 0 }
+            Curve::QuadraticBezier(q) => { push(q.a); push(q.b); push(q.c); 1 }
+            Curve::CubicBezier(c) => { push(c.a); push(c.b); push(c.c); push(c.d); 2 }
+            Curve::EllipticArc(a) => {
+                push(self.at(0.0)); push(self.at(1.0)); push(a.center); push(a.radii); push(a.crot);
+                key.push((a.dt / HASH_GRID).round() as i64);
+                3
+            }
+        };
+        (kind, key)
+    }
+}
+
+// Curves are equal if their coordinates snap to the same grid points; note that two coordinates
+// closer than the grid may still land on different points if they straddle a grid boundary
+impl PartialEq for Curve {
+    fn eq(&self, other: &Curve) -> bool { self.quantized_key() == other.quantized_key() }
+}
+
+impl Eq for Curve {}
+
+impl Hash for Curve {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.quantized_key().hash(state) }
+}
+
+// The cache tests need std's HashMap
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_curve_hashing() {
+        let c1 = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(2.0, 0.0));
+        let c2 = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0 + EPSILON / 8.0), Vec2::new(2.0, 0.0));
+        let line = Curve::line(Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0));
+        assert!(c1 == c2);
+        assert!(c1 != line && c1 != c1.reverse());
+
+        // The same arc with its starting angle wrapped around
+        let arc = Curve::circle(Vec2::zero(), 1.0, Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0), false);
+        let mut wrapped = arc;
+        if let Curve::EllipticArc(a) = &mut wrapped { a.t1 += 2.0 * CoordM::consts::PI; }
+        assert!(arc == wrapped);
+
+        let mut cache = HashMap::new();
+        cache.insert((c1, line), intersection_points(&c1, &line).len());
+        assert_eq!(cache.get(&(c2, line)), Some(&2));
+        assert_eq!(cache.get(&(line, c1)), None);
+    }
+}
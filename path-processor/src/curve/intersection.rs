@@ -7,11 +7,51 @@
 use crate::geometry::*;
 use super::*;
 
+// How the curves behave around an intersection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntersectionKind {
+    // The curves cross each other
+    Transversal,
+    // The curves touch each other, having the same tangent
+    Tangent,
+    // The intersection happens on an endpoint of one of the curves
+    Endpoint
+}
+
 #[derive(Debug)]
-pub struct IntersectionPair(pub Coord, pub Coord);
+pub struct IntersectionPair(pub Coord, pub Coord, pub IntersectionKind);
+
+// The tolerance for the tangents' cross product: tangential intersections are badly
+// conditioned, so their parameters are much less precise than the transversal ones
+const TANGENCY_EPSILON: Coord = 1.0 / 128.0;
+
+fn intersection_kind(curve1: &Curve, curve2: &Curve, t1: Coord, t2: Coord) -> IntersectionKind {
+    let is_endpoint = |t: Coord| t.roughly_zero() || t.roughly_equals(1.0);
+    if is_endpoint(t1) || is_endpoint(t2) { IntersectionKind::Endpoint }
+    else if curve1.tangent_at(t1).cross(curve2.tangent_at(t2)).abs() < TANGENCY_EPSILON { IntersectionKind::Tangent }
+    else { IntersectionKind::Transversal }
+}
+
+// The parameters that control the subdivision used to find the generic intersections
+#[derive(Debug, Clone, Copy)]
+pub struct IntersectionParams {
+    // The size below which a curve's bounding box is considered a point
+    pub precision: Coord,
+    // The maximum number of bisections; after that, the midpoint of the ranges is taken
+    pub max_depth: usize
+}
+
+impl Default for IntersectionParams {
+    fn default() -> Self { IntersectionParams { precision: EPSILON, max_depth: 48 } }
+}
 
 pub fn intersection(curve1: &Curve, curve2: &Curve, cp1: &CriticalPoints, cp2: &CriticalPoints)
     -> Vec<IntersectionPair> {
+    intersection_with_params(curve1, curve2, cp1, cp2, &IntersectionParams::default())
+}
+
+pub fn intersection_with_params(curve1: &Curve, curve2: &Curve, cp1: &CriticalPoints, cp2: &CriticalPoints,
+    params: &IntersectionParams) -> Vec<IntersectionPair> {
     let mut intersections = Vec::new();
 
     // Check all special cases
@@ -24,7 +64,7 @@ pub fn intersection(curve1: &Curve, curve2: &Curve, cp1: &CriticalPoints, cp2: &
             for root in ints.as_ref().iter().filter(|&&t| inside01(t)) {
                 let df = line1.b - line1.a;
                 let pos = df.dot(curve2.at(*root) - line1.a) / df.length_sq();
-                intersections.push(IntersectionPair(pos, *root));
+                intersections.push((pos, *root));
             }
         }
         (_, Curve::Line(line2)) => {
@@ -32,19 +72,97 @@ pub fn intersection(curve1: &Curve, curve2: &Curve, cp1: &CriticalPoints, cp2: &
             for root in ints.as_ref().iter().filter(|&&t| inside01(t)) {
                 let df = line2.b - line2.a;
                 let pos = df.dot(curve1.at(*root) - line2.a) / df.length_sq();
-                intersections.push(IntersectionPair(*root, pos));
+                intersections.push((*root, pos));
+            }
+        }
+        (Curve::QuadraticBezier(quad1), Curve::QuadraticBezier(quad2)) => {
+            if !intersection_quadratic_quadratic(&mut intersections, quad1, quad2) {
+                intersection_generic(&mut intersections, curve1, curve2, cp1, cp2, params);
             }
         }
         (_, _) => {
-            intersection_generic(&mut intersections, &curve1, &curve2, &cp1, &cp2);
+            intersection_generic(&mut intersections, &curve1, &curve2, &cp1, &cp2, params);
         }
     };
 
-    // return it
-    intersections
+    // Classify and return them
+    intersections.into_iter().map(|(t1, t2)| IntersectionPair(t1, t2, intersection_kind(curve1, curve2, t1, t2))).collect()
+}
+
+// An intersection between two curves, with both parameters and its position
+#[derive(Debug, Clone, Copy)]
+pub struct IntersectionRecord {
+    pub t1: Coord,
+    pub t2: Coord,
+    pub point: Vec2,
+    pub kind: IntersectionKind
+}
+
+// Get all the intersections between two curves inside their [0,1] ranges
+pub fn intersection_points(curve1: &Curve, curve2: &Curve) -> Vec<IntersectionRecord> {
+    let cp1 = curve1.critical_points();
+    let cp2 = curve2.critical_points();
+
+    let ints = intersection(curve1, curve2, &cp1, &cp2).into_iter();
+    ints.filter(|&IntersectionPair(t1, t2, _)| inside01(t1) && inside01(t2)).map(|IntersectionPair(t1, t2, kind)| {
+        // Average the positions on both curves, since they differ slightly
+        IntersectionRecord { t1, t2, point: (curve1.at(t1) + curve2.at(t2)) / 2.0, kind }
+    }).collect()
+}
+
+// Check whether two curves meet inside their [0,1] ranges, stopping on the first meeting point found
+pub fn curves_intersect(curve1: &Curve, curve2: &Curve) -> bool {
+    if !curve1.bbox().intersects(curve2.bbox()) { return false; }
+
+    match (curve1, curve2) {
+        (Curve::Line(l1), Curve::Line(l2)) => segments_intersect(l1.a, l1.b, l2.a, l2.b, false),
+        (Curve::Line(line), curve) | (curve, Curve::Line(line)) => {
+            let df = line.b - line.a;
+            if df.roughly_zero() { return curve_contains_point(curve, line.a); }
+            curve.intersection_seg(line.a, line.b).as_ref().iter()
+                .any(|&t| inside01(t) && inside01(df.dot(curve.at(t) - line.a) / df.length_sq()))
+        }
+        _ => {
+            let (cp1, cp2) = (curve1.critical_points(), curve2.critical_points());
+            let params = IntersectionParams::default();
+            cp1.windows(2).any(|i1| cp2.windows(2).any(|i2|
+                monotonous_pieces_intersect(curve1, curve2, i1[0], i1[1], i2[0], i2[1], &params, 0)))
+        }
+    }
 }
 
-fn intersection_line_line(out: &mut Vec<IntersectionPair>, l1: &line::Line, l2: &line::Line) {
+// Whether a degenerate line (a point) lies on the curve
+fn curve_contains_point(curve: &Curve, p: Vec2) -> bool {
+    curve.intersection_x(p.x).as_ref().iter().any(|&t| inside01(t) && curve.at(t).roughly_equals(p))
+        || curve.intersection_y(p.y).as_ref().iter().any(|&t| inside01(t) && curve.at(t).roughly_equals(p))
+}
+
+// The same subdivision as intersection_generic_monotonous, accepting the first pair of pieces
+// that becomes negligible while still overlapping
+#[allow(clippy::too_many_arguments)]
+fn monotonous_pieces_intersect(c1: &Curve, c2: &Curve, t1l: Coord, t1r: Coord, t2l: Coord, t2r: Coord,
+    params: &IntersectionParams, depth: usize) -> bool {
+    let bb1s = Rect::enclosing_rect_of_two_points(c1.at(t1l), c1.at(t1r));
+    let bb2s = Rect::enclosing_rect_of_two_points(c2.at(t2l), c2.at(t2r));
+    if !bb1s.intersects(bb2s) { return false; }
+
+    let is_rectangle_negligible = |r: Rect| r.width * 2.0 < params.precision && r.height * 2.0 < params.precision;
+    let r1 = is_rectangle_negligible(bb1s);
+    let r2 = is_rectangle_negligible(bb2s);
+    if (r1 && r2) || depth >= params.max_depth { return true; }
+
+    // When one of the pieces is negligible, only the other one is split
+    let halves = |l: Coord, r: Coord, negligible: bool| -> ArrayVec<[(Coord, Coord); 2]> {
+        if negligible { [(l, r)].iter().copied().collect() }
+        else { [(l, (l + r) / 2.0), ((l + r) / 2.0, r)].iter().copied().collect() }
+    };
+
+    let halves2 = halves(t2l, t2r, r2);
+    halves(t1l, t1r, r1).iter().any(|&(l1, r1)| halves2.iter().any(|&(l2, r2)|
+        monotonous_pieces_intersect(c1, c2, l1, r1, l2, r2, params, depth + 1)))
+}
+
+fn intersection_line_line(out: &mut Vec<(Coord, Coord)>, l1: &line::Line, l2: &line::Line) {
     // Check if both lines are subdividing
     let p = l1.a;
     let q = l2.a;
@@ -75,20 +193,20 @@ fn intersection_line_line(out: &mut Vec<IntersectionPair>, l1: &line::Line, l2:
                 // Assemble the lines accordingly (tedious cases...)
                 if 0.0 <= tab0 && tab0 <= 1.0 { // l1.a -- l2.a -- l1.b, with l2.b elsewhere
                     if tab1 > 1.0 { // l2.b to the right of l1
-                        out.append(&mut vec![IntersectionPair(tab0, 0.0), IntersectionPair(1.0, tba1)]);
+                        out.append(&mut vec![(tab0, 0.0), (1.0, tba1)]);
                     } else if tab1 < 0.0 { // l2.b to the left of l1
-                        out.append(&mut vec![IntersectionPair(tab0, 0.0), IntersectionPair(0.0, tba0)]);
+                        out.append(&mut vec![(tab0, 0.0), (0.0, tba0)]);
                     } else { // l2 inside l1
-                        out.append(&mut vec![IntersectionPair(tab0, 0.0), IntersectionPair(tab1, 1.0)]);
+                        out.append(&mut vec![(tab0, 0.0), (tab1, 1.0)]);
                     }
                 } else if 0.0 < tab1 && tab1 <= 1.0 { // l1.a -- l2.b -- l1.b with l2.a elsewhere
                     if tab0 < 0.0 { // l2.a to the left of l1
-                        out.append(&mut vec![IntersectionPair(0.0, tba0), IntersectionPair(tab1, 1.0)]);
+                        out.append(&mut vec![(0.0, tba0), (tab1, 1.0)]);
                     } else { // l2.a to the right of l1
-                        out.append(&mut vec![IntersectionPair(1.0, tba1), IntersectionPair(tab1, 1.0)]);
+                        out.append(&mut vec![(1.0, tba1), (tab1, 1.0)]);
                     }
                 } else { //l1 inside l2
-                    out.append(&mut vec![IntersectionPair(0.0, tba0), IntersectionPair(1.0, tba1)]);
+                    out.append(&mut vec![(0.0, tba0), (1.0, tba1)]);
                 }
             }
         }
@@ -96,27 +214,108 @@ fn intersection_line_line(out: &mut Vec<IntersectionPair>, l1: &line::Line, l2:
         // If they don't, calculate t and u
         let t = (q - p).cross(s) / k;
         let u = (q - p).cross(r) / k;
-        out.push(IntersectionPair(t, u));
+        out.push((t, u));
+    }
+}
+
+// Intersect two quadratics by implicitizing the second one: in the barycentric coordinates (l0, l1, l2)
+// of its control triangle, its points satisfy l1² = 4 l0 l2, and substituting the first quadratic there
+// gives a quartic on its parameter; returns false if the second quadratic is degenerate
+fn intersection_quadratic_quadratic(out: &mut Vec<(Coord, Coord)>, q1: &QuadraticBezier, q2: &QuadraticBezier) -> bool {
+    let e1 = q2.b - q2.a;
+    let e2 = q2.c - q2.a;
+    let det = e1.cross(e2);
+    if det.abs() <= EPSILON * e1.length() * e2.length() { return false; }
+
+    // The first quadratic written as a polynomial on t, relative to the second's starting point
+    let pa = q1.a - 2.0 * q1.b + q1.c;
+    let pb = 2.0 * (q1.b - q1.a);
+    let pc = q1.a - q2.a;
+
+    // The barycentric coordinates as quadratic polynomials on t (lowest degree first)
+    let l1 = [pc.cross(e2) / det, pb.cross(e2) / det, pa.cross(e2) / det];
+    let l2 = [e1.cross(pc) / det, e1.cross(pb) / det, e1.cross(pa) / det];
+    let l0 = [1.0 - l1[0] - l2[0], -l1[1] - l2[1], -l1[2] - l2[2]];
+
+    let mut poly = [0.0; 5];
+    for i in 0..3 {
+        for j in 0..3 {
+            poly[i+j] += l1[i] * l1[j] - 4.0 * l0[i] * l2[j];
+        }
+    }
+
+    // If the quartic vanishes, the curves lie on the same parabola
+    let scale = poly.iter().fold(0.0 as Coord, |m, c| m.max(c.abs()));
+    if scale.roughly_zero() { return false; }
+    for c in poly.iter_mut() { *c /= scale; }
+
+    for t in quartic_roots_in01(&poly) {
+        // On the parabola, l1/2 + l2 gives back the parameter of the second quadratic
+        let u = (l1[0] + t * (l1[1] + t * l1[2])) / 2.0 + l2[0] + t * (l2[1] + t * l2[2]);
+        let (t, u) = (snap01(t), snap01(u));
+        if inside01(t) && inside01(u) { out.push((t, u)); }
+    }
+
+    true
+}
+
+// Find the roots of a quartic (lowest degree first) on a slightly enlarged [0,1] range: the analytical
+// quartic formula is not reliable enough, so the range is split on the critical points and each
+// monotonous piece is bisected
+fn quartic_roots_in01(poly: &[Coord; 5]) -> Vec<Coord> {
+    let eval = |t: Coord| poly.iter().rev().fold(0.0, |acc, c| acc * t + c);
+
+    let (l, r) = (-EPSILON, 1.0 + EPSILON);
+    let critical = roots::find_roots_cubic(4.0 * poly[4], 3.0 * poly[3], 2.0 * poly[2], poly[1]);
+    let mut points = vec![l];
+    points.extend(critical.as_ref().iter().filter(|&&t| t > l && t < r));
+    points.push(r);
+    points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    // Tangential roots don't change the sign, so they can only be caught on the critical points
+    let mut roots: Vec<_> = points.iter().copied().filter(|&t| eval(t).abs() < EPSILON2).collect();
+    for w in points.windows(2) {
+        let (mut a, mut b) = (w[0], w[1]);
+        let sa = eval(a).signum();
+        if sa == eval(b).signum() { continue; }
+
+        for _ in 0..64 {
+            let m = (a + b) / 2.0;
+            if eval(m).signum() == sa { a = m; } else { b = m; }
+        }
+        roots.push((a + b) / 2.0);
     }
+
+    roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    roots.dedup_by(|a, b| a.roughly_equals(*b));
+    roots
+}
+
+// Pull the parameters that fell just outside of the range because of rounding back to it
+fn snap01(t: Coord) -> Coord {
+    if t < 0.0 && t.roughly_zero() { 0.0 }
+    else if t > 1.0 && t.roughly_equals(1.0) { 1.0 }
+    else { t }
 }
 
-fn intersection_generic(out: &mut Vec<IntersectionPair>, c1: &Curve, c2: &Curve,
-    cp1: &CriticalPoints, cp2: &CriticalPoints) {
+fn intersection_generic(out: &mut Vec<(Coord, Coord)>, c1: &Curve, c2: &Curve,
+    cp1: &CriticalPoints, cp2: &CriticalPoints, params: &IntersectionParams) {
     for i1 in cp1.windows(2) {
         for i2 in cp2.windows(2) {
-            intersection_generic_monotonous(out, c1, c2, i1[0], i1[1], i2[0], i2[1]);
+            intersection_generic_monotonous(out, c1, c2, i1[0], i1[1], i2[0], i2[1], params, 0);
         }
     }
 }
 
-fn intersection_generic_monotonous(out: &mut Vec<IntersectionPair>, c1: &Curve, c2: &Curve,
-    t1l: Coord, t1r: Coord, t2l: Coord, t2r: Coord) {
+#[allow(clippy::too_many_arguments)]
+fn intersection_generic_monotonous(out: &mut Vec<(Coord, Coord)>, c1: &Curve, c2: &Curve,
+    t1l: Coord, t1r: Coord, t2l: Coord, t2r: Coord, params: &IntersectionParams, depth: usize) {
 
     // Treat endpoints
-    if c1.at(t1l) == c2.at(t2l) { out.push(IntersectionPair(t1l, t2l)); }
-    if c1.at(t1l) == c2.at(t2r) { out.push(IntersectionPair(t1l, t2r)); }
-    if c1.at(t1r) == c2.at(t2l) { out.push(IntersectionPair(t1r, t2l)); }
-    if c1.at(t1r) == c2.at(t2r) { out.push(IntersectionPair(t1r, t2r)); }
+    if c1.at(t1l) == c2.at(t2l) { out.push((t1l, t2l)); }
+    if c1.at(t1l) == c2.at(t2r) { out.push((t1l, t2r)); }
+    if c1.at(t1r) == c2.at(t2l) { out.push((t1r, t2l)); }
+    if c1.at(t1r) == c2.at(t2r) { out.push((t1r, t2r)); }
 
     // Take the subcurves for the current iteration (use the fact that they are monotonous here)
     let bb1s = Rect::enclosing_rect_of_two_points(c1.at(t1l), c1.at(t1r));
@@ -128,39 +327,43 @@ fn intersection_generic_monotonous(out: &mut Vec<IntersectionPair>, c1: &Curve,
         let t1m = (t1l + t1r) / 2.0;
         let t2m = (t2l + t2r) / 2.0;
 
-        // Pick the right curves based on whether each rectangle is negligible
-        fn is_rectangle_negligible(r: Rect) -> bool { 
-            (r.width * 2.0).roughly_zero() && (r.height * 2.0).roughly_zero() 
+        // If we went too deep, just take the midpoints
+        if depth >= params.max_depth {
+            out.push((t1m, t2m));
+            return;
         }
 
+        // Pick the right curves based on whether each rectangle is negligible
+        let is_rectangle_negligible = |r: Rect| r.width * 2.0 < params.precision && r.height * 2.0 < params.precision;
+
         let r1 = is_rectangle_negligible(bb1s);
         let r2 = is_rectangle_negligible(bb2s);
 
         if !r1 && !r2 {
-            intersection_generic_monotonous(out, c1, c2, t1l, t1m, t2l, t2m);
-            intersection_generic_monotonous(out, c1, c2, t1l, t1m, t2m, t2r);
-            intersection_generic_monotonous(out, c1, c2, t1m, t1r, t2l, t2m);
-            intersection_generic_monotonous(out, c1, c2, t1m, t1r, t2m, t2r);
+            intersection_generic_monotonous(out, c1, c2, t1l, t1m, t2l, t2m, params, depth + 1);
+            intersection_generic_monotonous(out, c1, c2, t1l, t1m, t2m, t2r, params, depth + 1);
+            intersection_generic_monotonous(out, c1, c2, t1m, t1r, t2l, t2m, params, depth + 1);
+            intersection_generic_monotonous(out, c1, c2, t1m, t1r, t2m, t2r, params, depth + 1);
         } else if r1 && !r2 {
-            intersection_generic_monotonous(out, c1, c2, t1l, t1r, t2l, t2m);
-            intersection_generic_monotonous(out, c1, c2, t1l, t1r, t2m, t2r);
+            intersection_generic_monotonous(out, c1, c2, t1l, t1r, t2l, t2m, params, depth + 1);
+            intersection_generic_monotonous(out, c1, c2, t1l, t1r, t2m, t2r, params, depth + 1);
         } else if !r1 && r2 {
-            intersection_generic_monotonous(out, c1, c2, t1l, t1m, t2l, t2r);
-            intersection_generic_monotonous(out, c1, c2, t1m, t1r, t2l, t2r);
+            intersection_generic_monotonous(out, c1, c2, t1l, t1m, t2l, t2r, params, depth + 1);
+            intersection_generic_monotonous(out, c1, c2, t1m, t1r, t2l, t2r, params, depth + 1);
         } else { 
             // Pick the correct root points
             // Check for the endpoints
             if bb.contains_point(c1.at(t1l)) && bb.contains_point(c2.at(t2l)) {
-                out.push(IntersectionPair(t1l, t2l))
+                out.push((t1l, t2l))
             }
             if bb.contains_point(c1.at(t1l)) && bb.contains_point(c2.at(t2r)) {
-                out.push(IntersectionPair(t1l, t2r))
+                out.push((t1l, t2r))
             }
             if bb.contains_point(c1.at(t1r)) && bb.contains_point(c2.at(t2l)) {
-                out.push(IntersectionPair(t1r, t2l))
+                out.push((t1r, t2l))
             }
             if bb.contains_point(c1.at(t1r)) && bb.contains_point(c2.at(t2r)) {
-                out.push(IntersectionPair(t1r, t2r))
+                out.push((t1r, t2r))
             }
 
             let curve_roots = |curve: &Curve, tl, tr| {
@@ -181,9 +384,151 @@ fn intersection_generic_monotonous(out: &mut Vec<IntersectionPair>, c1: &Curve,
 
             if let Some(r1) = curve_roots(c1, t1l, t1r) {
                 if let Some(r2) = curve_roots(c2, t2l, t2r) {
-                    out.push(IntersectionPair(r1, r2));
+                    out.push((r1, r2));
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intersection_points() {
+        let line = Curve::line(Vec2::new(0.0, 1.0), Vec2::new(4.0, 1.0));
+        let quad = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(2.0, 4.0), Vec2::new(4.0, 0.0));
+
+        let mut points = intersection_points(&line, &quad);
+        points.sort_by(|a, b| a.t1.partial_cmp(&b.t1).unwrap());
+        assert_eq!(points.len(), 2);
+
+        for p in &points {
+            assert!(p.point.y.roughly_equals(1.0));
+            assert!(p.point.roughly_equals(quad.at(p.t2)));
+        }
+        assert!(points[0].point.x < points[1].point.x);
+    }
+
+    #[test]
+    fn test_intersection_precision() {
+        let c1 = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(2.0, 4.0), Vec2::new(4.0, 0.0));
+        let c2 = Curve::quadratic_bezier(Vec2::new(0.0, 3.0), Vec2::new(2.0, -1.0), Vec2::new(4.0, 3.0));
+        let (cp1, cp2) = (c1.critical_points(), c2.critical_points());
+
+        // Both a coarse precision and a shallow depth must still land close to the real intersections
+        let coarse = IntersectionParams { precision: 0.01, ..Default::default() };
+        let shallow = IntersectionParams { max_depth: 8, ..Default::default() };
+        for params in &[coarse, shallow] {
+            let ints = intersection_with_params(&c1, &c2, &cp1, &cp2, params);
+            assert!(!ints.is_empty());
+            for IntersectionPair(t1, t2, _) in ints {
+                assert!((c1.at(t1) - c2.at(t2)).length() < 0.1, "{:?}", params);
+            }
+        }
+    }
+
+    #[test]
+    fn test_intersection_kinds() {
+        let c1 = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(2.0, 4.0), Vec2::new(4.0, 0.0));
+
+        // A line touching the top of the curve and one crossing it
+        let touching = Curve::line(Vec2::new(0.0, 2.0), Vec2::new(4.0, 2.0));
+        let crossing = Curve::line(Vec2::new(0.0, 1.0), Vec2::new(4.0, 1.0));
+        // A curve starting at the other's end
+        let chained = Curve::line(Vec2::new(4.0, 0.0), Vec2::new(6.0, 1.0));
+
+        let kinds = |c2: &Curve| -> Vec<_> { intersection_points(&c1, c2).iter().map(|r| r.kind).collect() };
+        let touching_kinds = kinds(&touching);
+        assert!(!touching_kinds.is_empty() && touching_kinds.iter().all(|&k| k == IntersectionKind::Tangent));
+        assert_eq!(kinds(&crossing), vec![IntersectionKind::Transversal; 2]);
+        assert_eq!(kinds(&chained), vec![IntersectionKind::Endpoint]);
+    }
+
+    #[test]
+    fn test_quadratic_quadratic_fast_path() {
+        let pairs = [
+            (Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(2.0, 0.0)),
+                Curve::quadratic_bezier(Vec2::new(0.0, 1.0), Vec2::new(1.0, -1.0), Vec2::new(2.0, 1.0))),
+            (Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(3.0, 4.0), Vec2::new(5.0, -1.0)),
+                Curve::quadratic_bezier(Vec2::new(-1.0, 2.0), Vec2::new(2.0, -3.0), Vec2::new(4.0, 3.0))),
+            // The second curve starts on the interior of the first one
+            (Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(2.0, 0.0)),
+                Curve::quadratic_bezier(Vec2::new(1.0, 1.0), Vec2::new(2.0, 3.0), Vec2::new(3.0, 1.0)))
+        ];
+
+        for (c1, c2) in pairs.iter() {
+            let (cp1, cp2) = (c1.critical_points(), c2.critical_points());
+            let fast = intersection_points(c1, c2);
+
+            let mut generic = Vec::new();
+            intersection_generic(&mut generic, c1, c2, &cp1, &cp2, &IntersectionParams::default());
+            assert!(!fast.is_empty());
+
+            // Every fast intersection must lie on both curves, and the bisection must have found it too
+            for int in &fast {
+                assert!(c1.at(int.t1).roughly_equals(c2.at(int.t2)), "{:?}", int);
+                assert!(generic.iter().any(|&(t1, t2)| c1.at(t1).roughly_equals(int.point)
+                    && c2.at(t2).roughly_equals(int.point)), "{:?}", int);
+            }
+            for &(t1, t2) in &generic {
+                assert!(fast.iter().any(|int| int.point.roughly_equals(c1.at(t1))), "({}, {})", t1, t2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_arc_line_roots_in_range() {
+        // A rotated half ellipse, crossed by lines that hit it on zero, one or two points
+        let arc = Curve::elliptic_arc(Vec2::new(3.0, 0.0), Vec2::new(3.0, 2.0), 0.3, false, true, Vec2::new(-3.0, 0.0));
+        let lines = [(Vec2::new(-4.0, 0.5), Vec2::new(4.0, 0.7)), (Vec2::new(-4.0, -0.5), Vec2::new(4.0, -0.7)),
+            (Vec2::new(0.0, -5.0), Vec2::new(0.0, 5.0)), (Vec2::new(-4.0, 1.0), Vec2::new(4.0, 1.0)),
+            (Vec2::new(10.0, -5.0), Vec2::new(10.0, 5.0))];
+
+        let mut hits = 0;
+        for &(v1, v2) in lines.iter() {
+            let roots = arc.intersection_seg(v1, v2);
+            hits += roots.as_ref().len();
+            for &t in roots.as_ref() {
+                assert!(t.is_finite() && t > -EPSILON && t < 1.0 + EPSILON, "{}", t);
+                assert!((arc.at(t) - v1).cross(v2 - v1).roughly_zero(), "{} is not on the line", arc.at(t));
+            }
+        }
+        assert!(hits >= 3, "{}", hits);
+
+        for &x in [-2.0, 0.0, 1.5, 5.0].iter() {
+            for &t in arc.intersection_x(x).as_ref() {
+                assert!(t.is_finite() && arc.at(t).x.roughly_equals(x));
+            }
+        }
+        for &y in [-1.0, 0.5, 1.5, 5.0].iter() {
+            for &t in arc.intersection_y(y).as_ref() {
+                assert!(t.is_finite() && arc.at(t).y.roughly_equals(y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_curves_intersect() {
+        let curves = [
+            Curve::line(Vec2::new(0.0, 0.0), Vec2::new(4.0, 4.0)),
+            Curve::line(Vec2::new(0.0, 4.0), Vec2::new(1.0, 3.0)),
+            Curve::line(Vec2::new(3.0, 0.0), Vec2::new(4.0, 4.0)),
+            Curve::quadratic_bezier(Vec2::new(0.0, 3.0), Vec2::new(2.0, -1.0), Vec2::new(4.0, 3.0)),
+            Curve::cubic_bezier(Vec2::new(0.0, 1.0), Vec2::new(1.0, 5.0), Vec2::new(3.0, 5.0), Vec2::new(4.0, 1.0)),
+            Curve::cubic_bezier(Vec2::new(5.0, 0.0), Vec2::new(6.0, 2.0), Vec2::new(7.0, -2.0), Vec2::new(8.0, 0.0)),
+            Curve::elliptic_arc(Vec2::new(2.0, 1.0), Vec2::new(1.0, 1.0), 0.0, false, true, Vec2::new(0.0, 1.0)),
+            Curve::elliptic_arc(Vec2::new(6.0, 3.0), Vec2::new(1.0, 1.0), 0.0, false, true, Vec2::new(6.0, 5.0))
+        ];
+
+        // The predicate must agree with the full intersection routine
+        for i in 0..curves.len() {
+            for j in i+1..curves.len() {
+                let expected = !intersection_points(&curves[i], &curves[j]).is_empty();
+                assert_eq!(curves_intersect(&curves[i], &curves[j]), expected, "{} and {}", curves[i], curves[j]);
+                assert_eq!(curves_intersect(&curves[j], &curves[i]), expected, "{} and {}", curves[j], curves[i]);
+            }
+        }
+    }
+}
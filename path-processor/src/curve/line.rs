@@ -10,7 +10,7 @@ use crate::arrayvec::*;
 use super::*;
 
 // The line structure
-#[derive(Display)]
+#[derive(Copy, Clone, Display)]
 #[display(fmt = "Line({},{})", a, b)]
 pub struct Line { pub a: Vec2, pub b: Vec2 }
 
@@ -26,9 +26,14 @@ impl Line {
 
     pub fn reverse(&self) -> Line { Line {a: self.b, b: self.a } }
 
+    pub fn mirror(&self, axis_point: Vec2, axis_dir: Vec2) -> Line {
+        Line { a: self.a.reflect(axis_point, axis_dir), b: self.b.reflect(axis_point, axis_dir) }
+    }
+
     pub fn winding(&self) -> Coord { self.a.cross(self.b) }
 
-    pub fn angle_key(&self) -> AngleKey { AngleKey::new(self.a.angle_facing(self.b), 0.0, 0.0) }
+    #[cfg(feature = "std")]
+    pub fn angle_key(&self) -> AngleKey { AngleKey::new(self.a.angle_facing(self.b), 0.0, 0.0, 0.0) }
 
     pub fn intersection_x(&self, x: Coord) -> roots::Roots<Coord> {
         roots::find_roots_linear(self.b.x - self.a.x, self.a.x - x)
@@ -40,7 +45,22 @@ impl Line {
 
     pub fn intersection_seg(&self, v1: Vec2, v2: Vec2) -> roots::Roots<Coord> {
         let dv = v2 - v1;
-        roots::find_roots_linear(dv.cross(self.b - self.a), dv.cross(self.a - v1))
+        let df = self.b - self.a;
+
+        // If the line lies on the segment's support, report where their overlap begins and ends
+        if !dv.roughly_zero() && !df.roughly_zero() {
+            let dn = dv.normalized();
+            if dn.cross(df.normalized()).roughly_zero_squared() && dn.cross(self.a - v1).roughly_zero_squared() {
+                let t1 = (v1 - self.a).dot(df) / df.length_sq();
+                let t2 = (v2 - self.a).dot(df) / df.length_sq();
+                let (l, r) = (t1.min(t2).max(0.0), t1.max(t2).min(1.0));
+                return if l > r { roots::Roots::No([]) }
+                    else if l == r { roots::Roots::One([l]) }
+                    else { roots::Roots::Two([l, r]) };
+            }
+        }
+
+        roots::find_roots_linear(dv.cross(df), dv.cross(self.a - v1))
     }
 
     pub fn entry_tangent(&self) -> Vec2 { (self.b-self.a).normalized() }
@@ -51,7 +71,28 @@ impl Line {
         [self.a, self.b].into_iter().copied().collect()
     }
 
+    pub fn tight_bbox(&self) -> Rect { Rect::enclosing_rect_of_two_points(self.a, self.b) }
+
     pub fn critical_points(&self) -> ArrayVec<[Coord; MAX_CRITICAL_POINTS]> { 
         [0.0, 1.0].iter().copied().collect()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_collinear_intersection_seg() {
+        let line = Line { a: Vec2::new(0.0, 0.0), b: Vec2::new(4.0, 0.0) };
+        let roots = line.intersection_seg(Vec2::new(3.0, 0.0), Vec2::new(1.0, 0.0));
+        assert_eq!(roots.as_ref(), &[0.25, 0.75]);
+        let roots = line.intersection_seg(Vec2::new(-2.0, 0.0), Vec2::new(6.0, 0.0));
+        assert_eq!(roots.as_ref(), &[0.0, 1.0]);
+
+        // Collinear but disjoint, and merely parallel
+        assert!(line.intersection_seg(Vec2::new(5.0, 0.0), Vec2::new(6.0, 0.0)).as_ref().is_empty());
+        assert!(line.intersection_seg(Vec2::new(1.0, 1.0), Vec2::new(3.0, 1.0)).as_ref().is_empty());
+        assert_eq!(line.intersection_seg(Vec2::new(1.0, 1.0), Vec2::new(1.0, -1.0)).as_ref(), &[0.25]);
+    }
+}
@@ -14,16 +14,34 @@ mod elliptic_arc;
 mod elliptic_arc_gen;
 mod intersection;
 mod simplification;
+#[cfg(feature = "std")]
 mod angle_key;
+mod arc_length;
+mod moments;
+mod offset;
+mod flatten;
+mod hashing;
+mod projection;
 
 pub use intersection::*;
 pub use simplification::*;
+#[cfg(feature = "std")]
 pub use angle_key::*;
+pub use arc_length::*;
+
+pub use line::Line;
+pub use quadratic_bezier::QuadraticBezier;
+pub use cubic_bezier::{CubicBezier, CubicKind};
+pub use elliptic_arc::EllipticArc;
+pub use elliptic_arc_gen::{PathParamsArc, from_path_params_reporting};
 
 use crate::geometry::*;
 use crate::arrayvec::ArrayVec;
+use crate::roots;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec, string::String, format};
 
-#[derive(Display)]
+#[derive(Copy, Clone, Display)]
 pub enum Curve {
     Line(line::Line),
     QuadraticBezier(quadratic_bezier::QuadraticBezier),
@@ -54,12 +72,15 @@ pub const MAX_CRITICAL_POINTS: usize = 6;
 pub type PolygonVertices = ArrayVec<[Vec2; MAX_POLYGON_VERTICES]>;
 pub type CriticalPoints = ArrayVec<[Coord; MAX_CRITICAL_POINTS]>;
 
+// Each of the four sides of a rectangle can be crossed at most four times
+pub const MAX_RECT_INTERSECTIONS: usize = 16;
+pub type RectIntersections = ArrayVec<[Coord; MAX_RECT_INTERSECTIONS]>;
+
 impl Curve {
     // Forward the implementations that have similar signatures
     forward_to_curves! {
         at(t: Coord) -> Vec2;
         winding() -> Coord;
-        angle_key() -> AngleKey;
 
         intersection_x(x: Coord) -> roots::Roots<Coord>;
         intersection_y(y: Coord) -> roots::Roots<Coord>;
@@ -72,6 +93,23 @@ impl Curve {
         critical_points() -> CriticalPoints
     }
 
+    // The angle keys order the curves around the DCEL vertices, so they come with the paths
+    #[cfg(feature = "std")]
+    forward_to_curves! { angle_key() -> AngleKey }
+
+    // The (t0, t1) ranges between consecutive critical points, where the curve is monotonous in
+    // both x and y; they cover [0,1] in order
+    pub fn monotone_spans(&self) -> Vec<(Coord, Coord)> {
+        self.critical_points().windows(2).map(|w| (w[0], w[1])).collect()
+    }
+
+    // The curve split in pieces that sweep less than a full turn each, as the arcs' parameter
+    // functions assume; only the arcs with larger sweeps are split
+    pub fn split_full_turns(&self) -> impl Iterator<Item = Curve> {
+        let (curve, arc) = match *self { Curve::EllipticArc(a) => (None, Some(a)), c => (Some(c), None) };
+        curve.into_iter().chain(arc.into_iter().flat_map(|a| a.split_full_turns().map(Curve::EllipticArc)))
+    }
+
     // Derivative and subcurve are pathological cases, just forward them manually
     pub fn derivative(&self) -> Curve {
         match self {
@@ -82,6 +120,17 @@ impl Curve {
         }
     }
 
+    // The piece of the curve between l and r, or None if the parameters are out of order or out of
+    // [0,1]; parameters that drifted outside the range by less than EPSILON are clamped back to it
+    pub fn try_subcurve(&self, l: Coord, r: Coord) -> Option<Curve> {
+        let in_range = |t: Coord| (-EPSILON..=1.0 + EPSILON).contains(&t);
+        if !in_range(l) || !in_range(r) || l > r + EPSILON { return None; }
+
+        let l = l.clamp(0.0, 1.0);
+        Some(self.subcurve(l, r.clamp(l, 1.0)))
+    }
+
+    // The unchecked version of try_subcurve, which extrapolates the curve outside [0,1]
     pub fn subcurve(&self, l: Coord, r: Coord) -> Curve {
         match self {
             Curve::Line(ln) => Curve::Line(ln.subcurve(l, r)),
@@ -100,8 +149,83 @@ impl Curve {
         }
     }
 
+    // Reflect the curve across the line that passes through axis_point with direction axis_dir
+    pub fn mirror(&self, axis_point: Vec2, axis_dir: Vec2) -> Curve {
+        match self {
+            Curve::Line(l) => Curve::Line(l.mirror(axis_point, axis_dir)),
+            Curve::QuadraticBezier(q) => Curve::QuadraticBezier(q.mirror(axis_point, axis_dir)),
+            Curve::CubicBezier(c) => Curve::CubicBezier(c.mirror(axis_point, axis_dir)),
+            Curve::EllipticArc(a) => Curve::EllipticArc(a.mirror(axis_point, axis_dir))
+        }
+    }
+
+    // Convert the curve to cubics, for the consumers that handle a single curve type: lines become
+    // cubics with control points on their endpoints, quadratics are elevated, cubics pass through
+    // and arcs are approximated within the tolerance. Note that the cubics coming from lines still
+    // need simplify_curves (or CubicBezier::classify) to be recognized as lines
+    pub fn as_cubics(&self, tolerance: Coord) -> Vec<CubicBezier> {
+        match self {
+            Curve::Line(l) => vec![CubicBezier { a: l.a, b: l.a, c: l.b, d: l.b }],
+            Curve::QuadraticBezier(q) => vec![q.elevate()],
+            Curve::CubicBezier(c) => vec![*c],
+            Curve::EllipticArc(a) => a.to_cubics(tolerance)
+        }
+    }
+
+    // The unit tangent of the curve at parameter t
+    pub fn tangent_at(&self, t: Coord) -> Vec2 {
+        let d = self.derivative().at(t);
+        if !d.roughly_zero() { d.normalized() }
+        else if t <= 0.0 { self.entry_tangent() }
+        else if t >= 1.0 { self.exit_tangent() }
+        // On a cusp, the direction is given by the second derivative
+        else { self.derivative().derivative().at(t).normalized() }
+    }
+
+    // Sample n uniformly spaced parameters from 0 to 1 inclusive, yielding (t, point, unit tangent)
+    pub fn samples(&self, n: usize) -> impl Iterator<Item = (Coord, Vec2, Vec2)> + '_ {
+        (0..n).map(move |i| {
+            let t = if n > 1 { i as Coord / (n - 1) as Coord } else { 0.0 };
+            (t, self.at(t), self.tangent_at(t))
+        })
+    }
+
+    // The exact bounding box, computed in closed form for each kind of curve; it always contains
+    // both endpoints, even for curves so flat that their critical points collapse
     pub fn bbox(&self) -> Rect {
-        Rect::enclosing_rect(self.critical_points().iter().map(|&t| self.at(t))).unwrap()
+        match self {
+            Curve::Line(l) => l.tight_bbox(),
+            Curve::QuadraticBezier(q) => q.tight_bbox(),
+            Curve::CubicBezier(c) => c.tight_bbox(),
+            Curve::EllipticArc(a) => a.tight_bbox()
+        }
+    }
+
+    // The box enclosing the critical points, which is None if the curve has none
+    pub fn try_bbox(&self) -> Option<Rect> {
+        Rect::enclosing_rect(self.critical_points().iter().map(|&t| self.at(t)))
+    }
+
+    // The parameters, in increasing order, where the curve crosses (or touches) the boundary of the
+    // rectangle; each side is intersected as a whole line, and the hits outside its span dropped,
+    // so the crossings through a corner are found twice and must be merged
+    pub fn intersect_rect(&self, r: Rect) -> RectIntersections {
+        let (min, max) = r.corners();
+        let on_span = |v: Coord, lo: Coord, hi: Coord| v >= lo - EPSILON && v <= hi + EPSILON;
+
+        let mut ts = RectIntersections::new();
+        for &x in &[min.x, max.x] {
+            let roots = self.intersection_x(x);
+            ts.extend(roots.as_ref().iter().copied().filter(|&t| inside01(t) && on_span(self.at(t).y, min.y, max.y)));
+        }
+        for &y in &[min.y, max.y] {
+            let roots = self.intersection_y(y);
+            ts.extend(roots.as_ref().iter().copied().filter(|&t| inside01(t) && on_span(self.at(t).x, min.x, max.x)));
+        }
+
+        ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        crate::vec_utils::arrayvec_dedup_by(&mut ts, |&a, &b| a.roughly_equals(b));
+        ts
     }
 
     // Create curves of specific types
@@ -118,15 +242,42 @@ impl Curve {
     pub fn circle(center: Vec2, radius: Coord, v1: Vec2, v2: Vec2, ccw: bool) -> Curve {
         Curve::EllipticArc(elliptic_arc_gen::circle(center, radius, v1, v2, ccw))
     }
+    pub fn full_circle(center: Vec2, radius: Coord, start: Vec2, ccw: bool) -> [Curve; 2] {
+        let [a, b] = elliptic_arc_gen::full_circle(center, radius, start, ccw);
+        [Curve::EllipticArc(a), Curve::EllipticArc(b)]
+    }
+
+    // Checked versions of the constructors, which reject non-finite coordinates
+    pub fn line_checked(a: Vec2, b: Vec2) -> Option<Curve> {
+        if all_finite(&[a, b]) { Some(Curve::line(a, b)) } else { None }
+    }
+    pub fn quadratic_bezier_checked(a: Vec2, b: Vec2, c: Vec2) -> Option<Curve> {
+        if all_finite(&[a, b, c]) { Some(Curve::quadratic_bezier(a, b, c)) } else { None }
+    }
+    pub fn cubic_bezier_checked(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Option<Curve> {
+        if all_finite(&[a, b, c, d]) { Some(Curve::cubic_bezier(a, b, c, d)) } else { None }
+    }
+    pub fn elliptic_arc_checked(cur: Vec2, radii: Vec2, rot: Coord, large_arc: bool, sweep: bool, target: Vec2) -> Option<Curve> {
+        if all_finite(&[cur, radii, target]) && rot.is_finite() {
+            Some(Curve::elliptic_arc(cur, radii, rot, large_arc, sweep, target))
+        } else { None }
+    }
 
     // No curve
     pub fn none() -> Curve { Curve::line(Vec2::zero(), Vec2::zero()) }
 
     // Some utility functions
+    // The winding integral ∫ (p - v) × p' dt taken about v instead of the origin, which is twice
+    // the signed area swept by the segment from v to the curve's point as it runs along the curve
     pub fn winding_relative_to(&self, v: Vec2) -> Coord {
         self.winding() - v.cross(self.at(1.0) - self.at(0.0))
     }
 
+    // The signed area swept from the origin given, positive where the curve turns counterclockwise
+    // around it; about a point of its chord, this is the area between the curve and the chord
+    pub fn signed_area_about(&self, origin: Vec2) -> Coord { self.winding_relative_to(origin) / 2.0 }
+
+    // Twice the signed area between the curve and its chord, the quantity the convexity depends on
     pub fn winding_at_midpoint(&self) -> Coord {
         self.winding_relative_to((self.at(0.0) + self.at(1.0)) / 2.0)
     }
@@ -140,6 +291,22 @@ impl Curve {
         }
     }
 
+    // Check if two curves describe the same geometry in the same direction
+    pub fn roughly_equals(&self, other: &Curve) -> bool {
+        match (self, other) {
+            (Curve::Line(l1), Curve::Line(l2)) => l1.a.roughly_equals(l2.a) && l1.b.roughly_equals(l2.b),
+            (Curve::QuadraticBezier(q1), Curve::QuadraticBezier(q2)) => q1.a.roughly_equals(q2.a)
+                && q1.b.roughly_equals(q2.b) && q1.c.roughly_equals(q2.c),
+            (Curve::CubicBezier(c1), Curve::CubicBezier(c2)) => c1.a.roughly_equals(c2.a)
+                && c1.b.roughly_equals(c2.b) && c1.c.roughly_equals(c2.c) && c1.d.roughly_equals(c2.d),
+            // The arcs' starting angles may differ by whole turns, so compare the endpoints instead
+            (Curve::EllipticArc(a1), Curve::EllipticArc(a2)) => a1.center.roughly_equals(a2.center)
+                && a1.radii.roughly_equals(a2.radii) && a1.crot.roughly_equals(a2.crot)
+                && a1.dt.roughly_equals(a2.dt) && a1.at(0.0).roughly_equals(a2.at(0.0)),
+            _ => false
+        }
+    }
+
     #[allow(dead_code)]
     pub fn path_command(&self) -> String {
         match self {
@@ -151,19 +318,250 @@ impl Curve {
     }
 }
 
-impl std::clone::Clone for Curve {
-    fn clone(&self) -> Self {
-        match self {
-            Curve::Line(l) => Curve::Line(line::Line { ..*l }),
-            Curve::QuadraticBezier(q) => Curve::QuadraticBezier(quadratic_bezier::QuadraticBezier { ..*q }),
-            Curve::CubicBezier(c) => Curve::CubicBezier(cubic_bezier::CubicBezier { ..*c }),
-            Curve::EllipticArc(a) => Curve::EllipticArc(elliptic_arc::EllipticArc { ..*a })
-        }
-    }
+// The convex hull of a set of curves, in counterclockwise order; it is built from their enclosing
+// polygons, which bound them, so it contains the curves entirely
+pub fn convex_hull_of_curves(curves: &[Curve]) -> Vec<Vec2> {
+    convex_hull(curves.iter().flat_map(|c| c.enclosing_polygon()).collect())
 }
 
-impl std::fmt::Debug for Curve {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+fn all_finite(vs: &[Vec2]) -> bool { vs.iter().all(|v| v.is_finite()) }
+
+impl core::fmt::Debug for Curve {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_convex_hull_of_curves() {
+        let curves = [
+            Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(2.0, 4.0), Vec2::new(4.0, 0.0)),
+            Curve::circle(Vec2::new(4.0, -1.0), 1.0, Vec2::new(0.0, 1.0), Vec2::new(0.0, -1.0), true),
+            Curve::line(Vec2::new(4.0, -2.0), Vec2::new(0.0, 0.0))
+        ];
+        let hull = convex_hull_of_curves(&curves);
+        assert!(polygon_winding(&hull) > 0.0);
+
+        // Every point of the curves must be inside the hull or on its boundary
+        for curve in curves.iter() {
+            for (_, p, _) in curve.samples(33) {
+                let inside = (0..hull.len()).all(|i| (hull[(i+1) % hull.len()] - hull[i]).cross(p - hull[i]) >= -EPSILON);
+                assert!(inside, "{} {}", curve, p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_area_about() {
+        use CoordM::consts::PI;
+
+        // A counterclockwise semicircle sweeps half of its disk about its center
+        let center = Vec2::new(3.0, -2.0);
+        let semicircle = Curve::circle(center, 2.0, Vec2::new(1.0, 0.0), Vec2::new(-1.0, 0.0), false);
+        assert!(semicircle.signed_area_about(center).roughly_equals(2.0 * PI));
+        assert!(semicircle.reverse().signed_area_about(center).roughly_equals(-2.0 * PI));
+        assert!(semicircle.is_convex());
+
+        // Any point on the line through the chord gives the same area, but not the other points
+        assert!(semicircle.signed_area_about(center + Vec2::new(7.0, 0.0)).roughly_equals(2.0 * PI));
+        assert!(semicircle.signed_area_about(center + Vec2::new(0.0, 1.0)).roughly_equals(2.0 * PI - 2.0));
+
+        // A line sweeps nothing about its own points, and a triangle about the others
+        let line = Curve::line(Vec2::new(1.0, 1.0), Vec2::new(3.0, 2.0));
+        assert!(line.signed_area_about(Vec2::new(5.0, 3.0)).roughly_zero());
+        assert!(line.signed_area_about(Vec2::new(1.0, 2.0)).roughly_equals(1.0));
+    }
+
+    #[test]
+    fn test_monotone_spans() {
+        let curve = Curve::cubic_bezier(Vec2::new(0.0, 0.0), Vec2::new(4.0, 1.0), Vec2::new(-1.0, 1.0), Vec2::new(3.0, 0.0));
+        let spans = curve.monotone_spans();
+        assert_eq!(spans.len(), curve.critical_points().len() - 1);
+        assert!(spans[0].0 == 0.0 && spans[spans.len()-1].1 == 1.0);
+
+        // Each span must be monotonous on both axes
+        for &(t0, t1) in &spans {
+            let points: Vec<_> = (0..=16).map(|i| curve.at(t0 + (t1 - t0) * i as Coord / 16.0)).collect();
+            for axis in &[|v: Vec2| v.x, |v: Vec2| v.y] {
+                let deltas: Vec<_> = points.windows(2).map(|w| axis(w[1]) - axis(w[0])).collect();
+                assert!(deltas.iter().all(|&d| d >= -EPSILON) || deltas.iter().all(|&d| d <= EPSILON));
+            }
+        }
+
+        let line = Curve::line(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0));
+        assert_eq!(line.monotone_spans(), vec![(0.0, 1.0)]);
+    }
+
+    #[test]
+    fn test_try_subcurve() {
+        let curve = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(2.0, 0.0));
+        let sub = curve.try_subcurve(0.25, 0.5).unwrap();
+        assert!(sub.at(0.0).roughly_equals(curve.at(0.25)) && sub.at(1.0).roughly_equals(curve.at(0.5)));
+
+        // Small drifts are clamped, anything else is rejected
+        let sub = curve.try_subcurve(-EPSILON / 2.0, 1.0 + EPSILON / 2.0).unwrap();
+        assert!(sub.at(0.0) == curve.at(0.0) && sub.at(1.0) == curve.at(1.0));
+        assert!(curve.try_subcurve(0.5, 0.5 - EPSILON / 2.0).is_some());
+        assert!(curve.try_subcurve(-0.1, 0.5).is_none());
+        assert!(curve.try_subcurve(0.5, 1.1).is_none());
+        assert!(curve.try_subcurve(0.75, 0.25).is_none());
+    }
+
+    #[test]
+    fn test_tight_bbox() {
+        let rot = Vec2::new(0.6, 0.8);
+        let curves = [
+            Curve::line(Vec2::new(2.0, 1.0), Vec2::new(-1.0, 3.0)),
+            Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 3.0), Vec2::new(3.0, -1.0)),
+            Curve::cubic_bezier(Vec2::new(0.0, 0.0), Vec2::new(4.0, 1.0), Vec2::new(-1.0, 1.0), Vec2::new(3.0, 0.0)),
+            Curve::EllipticArc(elliptic_arc::EllipticArc { center: Vec2::new(1.0, -1.0), radii: Vec2::new(3.0, 1.0),
+                crot: rot, t1: 0.3, dt: -5.0 }),
+            // Flat enough that the extremum is lost, but the endpoints still bound it
+            Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1e-12), Vec2::new(2.0, 0.0))
+        ];
+
+        for curve in &curves {
+            let bbox = curve.bbox();
+            let sampled = Rect::enclosing_rect((0..=1000).map(|i| curve.at(i as Coord / 1000.0))).unwrap();
+            assert!(bbox.contains_point(curve.at(0.0)) && bbox.contains_point(curve.at(1.0)), "{}", curve);
+
+            // The sampled box is inside the exact one, and only slightly smaller
+            let ((smin, smax), (bmin, bmax)) = (sampled.corners(), bbox.corners());
+            assert!(bbox.union(sampled).corners() == (bmin, bmax), "{}", curve);
+            assert!((smin - bmin).length() < 1e-4 && (smax - bmax).length() < 1e-4, "{}", curve);
+        }
+    }
+
+    #[test]
+    fn test_arc_critical_points_bbox() {
+        let arc = |t1: Coord, dt: Coord| Curve::EllipticArc(elliptic_arc::EllipticArc { center: Vec2::new(1.0, 2.0),
+            radii: Vec2::new(3.0, 1.0), crot: Vec2::new(0.8, -0.6), t1, dt });
+        let ax = (0.6 as Coord).atan2(2.4);
+
+        // Arcs far from the first turn, and thin arcs ending just past (or just before) an extremum
+        let arcs = [arc(0.3, 2.0), arc(20.0, 3.0), arc(-100.0, -4.0), arc(ax - 0.5, 0.5 + 1e-15),
+            arc(ax + 1e-15, -0.5), arc(ax - 1e-3, 2e-3), arc(ax + CoordM::consts::PI - 1e-9, 2e-9)];
+
+        for curve in &arcs {
+            let bbox = curve.try_bbox().unwrap();
+            let (min, max) = bbox.corners();
+            for i in 0..=1000 {
+                let p = curve.at(i as Coord / 1000.0);
+                assert!(p.x >= min.x - 1e-12 && p.y >= min.y - 1e-12 && p.x <= max.x + 1e-12 && p.y <= max.y + 1e-12,
+                    "{} outside {:?} for {}", p, bbox, curve);
+            }
+        }
+    }
+
+    #[test]
+    fn test_degenerate_bbox() {
+        // A point-like line and an arc with no radii have a zero-size box on their starting point
+        let p = Vec2::new(2.0, -1.0);
+        let curves = [Curve::line(p, p), Curve::EllipticArc(elliptic_arc::EllipticArc { center: p, radii: Vec2::zero(),
+            crot: Vec2::new(1.0, 0.0), t1: 0.5, dt: 2.0 })];
+
+        for curve in &curves {
+            assert!(curve.at(0.0).roughly_equals(p), "{}", curve);
+            for bbox in &[curve.bbox(), curve.try_bbox().unwrap()] {
+                let (min, max) = bbox.corners();
+                assert!(min.roughly_equals(p) && max.roughly_equals(p), "{:?} for {}", bbox, curve);
+            }
+        }
+    }
+
+    #[test]
+    fn test_intersect_rect() {
+        let rect = Rect::new(0.0, 0.0, 2.0, 2.0);
+
+        // A diagonal through two of the corners only crosses the boundary there
+        let diagonal = Curve::line(Vec2::new(-1.0, -1.0), Vec2::new(3.0, 3.0));
+        let ts = diagonal.intersect_rect(rect);
+        assert_eq!(ts.len(), 2);
+        assert!(ts[0].roughly_equals(0.25) && ts[1].roughly_equals(0.75));
+
+        // A curve that misses the rectangle, even though it crosses the lines of its sides
+        let outside = Curve::line(Vec2::new(3.0, -1.0), Vec2::new(5.0, 3.0));
+        assert!(outside.intersect_rect(rect).is_empty());
+
+        // An S-shaped cubic that goes in and out of the rectangle
+        let cubic = Curve::cubic_bezier(Vec2::new(-1.0, 1.0), Vec2::new(4.0, 4.0), Vec2::new(-2.0, -2.0), Vec2::new(3.0, 1.0));
+        let ts = cubic.intersect_rect(rect);
+        for w in ts.windows(2) { assert!(w[0] < w[1]); }
+        for &t in &ts {
+            let (p, (min, max)) = (cubic.at(t), rect.corners());
+            let on_side = p.x.roughly_equals(min.x) || p.x.roughly_equals(max.x)
+                || p.y.roughly_equals(min.y) || p.y.roughly_equals(max.y);
+            assert!(on_side && rect.clamp_point(p).roughly_equals(p), "{}", p);
+        }
+
+        // Between consecutive crossings, the curve is either all inside or all outside
+        let mut bounds = vec![0.0];
+        bounds.extend_from_slice(&ts);
+        bounds.push(1.0);
+        for w in bounds.windows(2) {
+            let inside = |t: Coord| rect.contains_point(cubic.at(t));
+            let mid = (w[0] + w[1]) / 2.0;
+            for i in 1..10 {
+                assert_eq!(inside(w[0] + (w[1] - w[0]) * i as Coord / 10.0), inside(mid));
+            }
+        }
+        assert!(ts.len() >= 2);
+    }
+
+    #[test]
+    fn test_samples() {
+        let curve = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(2.0, 0.0));
+        assert_eq!(curve.samples(0).count(), 0);
+
+        let start: Vec<_> = curve.samples(1).collect();
+        assert_eq!(start.len(), 1);
+        assert_eq!(start[0].0, 0.0);
+
+        let samples: Vec<_> = curve.samples(5).collect();
+        let ts: Vec<_> = samples.iter().map(|s| s.0).collect();
+        assert_eq!(ts, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        for &(t, p, tangent) in &samples {
+            assert!(p.roughly_equals(curve.at(t)));
+            assert!((tangent.length() - 1.0).abs() < 1e-9);
+        }
+        assert!(samples[2].2.roughly_equals(Vec2::new(1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_as_cubics() {
+        let line = Curve::line(Vec2::new(0.0, 0.0), Vec2::new(2.0, 1.0));
+        let cubics = line.as_cubics(1e-3);
+        assert_eq!(cubics.len(), 1);
+        assert_eq!(cubics[0].classify(), CubicKind::Line);
+
+        let quadratic = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(2.0, 0.0));
+        let cubic = Curve::CubicBezier(quadratic.as_cubics(1e-3)[0]);
+        for i in 0..=4 {
+            let t = i as Coord / 4.0;
+            assert!(cubic.at(t).roughly_equals(quadratic.at(t)));
+        }
+
+        // The cubics of a circle stay within the tolerance of it, and more are needed for smaller tolerances
+        let circle = Curve::circle(Vec2::new(1.0, 1.0), 3.0, Vec2::new(1.0, 0.0), Vec2::new(0.0, -1.0), false);
+        for &tolerance in [1e-2, 1e-4, 1e-6].iter() {
+            for c in circle.as_cubics(tolerance) {
+                let c = Curve::CubicBezier(c);
+                for i in 0..=16 {
+                    let d = (c.at(i as Coord / 16.0) - Vec2::new(1.0, 1.0)).length();
+                    assert!((d - 3.0).abs() <= tolerance);
+                }
+            }
+        }
+        assert_eq!(circle.as_cubics(1e-2).len(), 3);
+        assert!(circle.as_cubics(1e-6).len() > 3);
+
+        let arc = Curve::elliptic_arc(Vec2::new(2.0, 0.0), Vec2::new(2.0, 1.0), 0.4, true, false, Vec2::new(0.0, 1.0));
+        let cubics = arc.as_cubics(1e-4);
+        assert!(cubics[0].a.roughly_equals(arc.at(0.0)));
+        assert!(cubics[cubics.len() - 1].d.roughly_equals(arc.at(1.0)));
+    }
+}
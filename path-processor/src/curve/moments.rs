@@ -0,0 +1,84 @@
+//--------------------------------------------------------------------
+// moments.rs
+//--------------------------------------------------------------------
+// Provides the first moment integrals of the curves, which together
+// with the windings give the centroids of the regions they enclose
+//--------------------------------------------------------------------
+
+use crate::geometry::*;
+use super::*;
+use super::arc_length::GAUSS_LEGENDRE;
+
+impl Curve {
+    // The integral of p (p × p') along the curve, the first moment analogue of the winding:
+    // by Green's theorem, summing it over a closed contour gives three times the integrals
+    // of x and y over the enclosed region
+    pub fn moments(&self) -> Vec2 {
+        match self {
+            Curve::Line(l) => l.a.cross(l.b) * (l.a + l.b) / 2.0,
+            // The integrand is a polynomial of degree at most 7, so the quadrature is exact
+            Curve::QuadraticBezier(_) | Curve::CubicBezier(_) => {
+                let derivative = self.derivative();
+                GAUSS_LEGENDRE.iter().map(|&(x, w)| {
+                    let t = (x + 1.0) / 2.0;
+                    let p = self.at(t);
+                    w * p.cross(derivative.at(t)) * p
+                }).fold(Vec2::zero(), |acc, v| acc + v) / 2.0
+            }
+            Curve::EllipticArc(a) => arc_moments(a)
+        }
+    }
+
+    pub fn moment_x(&self) -> Coord { self.moments().x }
+    pub fn moment_y(&self) -> Coord { self.moments().y }
+}
+
+// Writing the arc as p = c + R e(θ), with e(θ) = (rx cos θ, ry sin θ), the integrand splits into
+// c (c × p') + rx ry (c + R e) + R e (u × e'), where u = R⁻¹c, and each term integrates exactly
+fn arc_moments(a: &EllipticArc) -> Vec2 {
+    let (rx, ry) = (a.radii.x, a.radii.y);
+    let (th0, th1) = (a.t1, a.t1 + a.dt);
+    let u = Vec2::new(a.crot.x, -a.crot.y).rot_scale(a.center);
+
+    let e = |th: Coord| Vec2::new(rx * th.sin(), -ry * th.cos());
+    let g = |th: Coord| {
+        let (s, c) = th.sin_cos();
+        Vec2::new(rx * ry * u.x * (th / 2.0 + s * c / 2.0) + rx * rx * u.y * s * s / 2.0,
+            ry * ry * u.x * s * s / 2.0 + rx * ry * u.y * (th / 2.0 - s * c / 2.0))
+    };
+
+    let chord = a.at(1.0) - a.at(0.0);
+    a.center * (a.center.cross(chord) + rx * ry * a.dt)
+        + a.crot.rot_scale(rx * ry * (e(th1) - e(th0)) + g(th1) - g(th0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Integrate the moments numerically, subdividing the curve
+    fn numeric_moments(curve: &Curve) -> Vec2 {
+        let n = 4096;
+        let derivative = curve.derivative();
+        (0..n).map(|i| {
+            let t = (i as Coord + 0.5) / n as Coord;
+            let p = curve.at(t);
+            p.cross(derivative.at(t)) * p / n as Coord
+        }).fold(Vec2::zero(), |acc, v| acc + v)
+    }
+
+    #[test]
+    fn test_moments() {
+        let curves = [
+            Curve::line(Vec2::new(1.0, 2.0), Vec2::new(-3.0, 0.5)),
+            Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(2.0, 3.0), Vec2::new(4.0, -1.0)),
+            Curve::cubic_bezier(Vec2::new(1.0, 1.0), Vec2::new(3.0, 4.0), Vec2::new(-2.0, 2.0), Vec2::new(0.0, -1.0)),
+            Curve::elliptic_arc(Vec2::new(3.0, 1.0), Vec2::new(2.0, 1.0), 0.5, false, true, Vec2::new(1.0, 2.0)),
+            Curve::elliptic_arc(Vec2::new(3.0, 1.0), Vec2::new(2.0, 1.0), -0.3, true, false, Vec2::new(1.0, 2.0))
+        ];
+
+        for curve in curves.iter() {
+            assert!((curve.moments() - numeric_moments(curve)).length() < 1e-4, "{}", curve);
+        }
+    }
+}
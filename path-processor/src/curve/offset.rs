@@ -0,0 +1,196 @@
+//--------------------------------------------------------------------
+// offset.rs
+//--------------------------------------------------------------------
+// Provides the offset curves, which approximate the curves displaced
+// along their normals by a fixed distance
+//--------------------------------------------------------------------
+
+use crate::geometry::*;
+use super::*;
+use CoordM::consts::PI;
+
+// The maximum number of times an offset piece is split, guarding against tiny tolerances
+const MAX_OFFSET_DEPTH: usize = 16;
+
+// The number of samples used to look for the cusps of the offset
+const OFFSET_CUSP_SAMPLES: usize = 32;
+
+// The number of bisection steps used to find each cusp
+const OFFSET_CUSP_ITERATIONS: usize = 40;
+
+impl Curve {
+    // The curves that approximate, within the tolerance, the curve displaced by distance along its
+    // left (counterclockwise) normal; negative distances displace it to the right. Lines and
+    // circular arcs have exact offsets, the other curves are approximated by cubics, split where
+    // the distance reaches the radius of curvature (as the offset has a cusp there)
+    pub fn offset(&self, distance: Coord, tolerance: Coord) -> Vec<Curve> {
+        match self {
+            Curve::Line(l) => {
+                if l.a.roughly_equals(l.b) { return Vec::new(); }
+                let n = distance * (l.b - l.a).normalized().ccw_perpendicular();
+                vec![Curve::line(l.a + n, l.b + n)]
+            }
+            Curve::EllipticArc(a) if (a.radii.x - a.radii.y).abs() <= EPSILON * a.radii.x.max(a.radii.y) =>
+                vec![Curve::EllipticArc(offset_circular_arc(a, distance))],
+            _ => {
+                let offset = OffsetCurve::new(self, distance);
+                let mut params = vec![0.0];
+                params.extend(offset.cusps());
+                params.push(1.0);
+
+                let mut out = Vec::new();
+                for w in params.windows(2) {
+                    offset.approximate(&mut out, w[0], w[1], tolerance, 0);
+                }
+                out
+            }
+        }
+    }
+}
+
+// A circle moves its points along the radius, so its offset is a concentric arc; if the
+// distance is larger than the radius, the arc flips to the opposite side of the center
+fn offset_circular_arc(a: &EllipticArc, distance: Coord) -> EllipticArc {
+    // The left normal points to the center on counterclockwise arcs
+    let radius = a.radii.x - distance * a.dt.signum();
+    let t1 = if radius < 0.0 { a.t1 + PI } else { a.t1 };
+    EllipticArc { radii: Vec2::new(radius.abs(), radius.abs()), t1, ..*a }
+}
+
+struct OffsetCurve<'a> {
+    curve: &'a Curve,
+    d1: Curve, d2: Curve,
+    distance: Coord
+}
+
+impl<'a> OffsetCurve<'a> {
+    fn new(curve: &'a Curve, distance: Coord) -> OffsetCurve<'a> {
+        let d1 = curve.derivative();
+        let d2 = d1.derivative();
+        OffsetCurve { curve, d1, d2, distance }
+    }
+
+    // How much the offset stretches the curve's derivative, 1 - dκ (where κ is the signed curvature);
+    // it is not defined where the derivative vanishes
+    fn stretch(&self, t: Coord) -> Option<Coord> {
+        let dp = self.d1.at(t);
+        let len = dp.length();
+        if len.roughly_zero_squared() { None }
+        else { Some(1.0 - self.distance * dp.cross(self.d2.at(t)) / (len * len * len)) }
+    }
+
+    // The point of the offset curve and its derivative
+    fn at(&self, t: Coord) -> (Vec2, Vec2) {
+        let pos = self.curve.at(t) + self.distance * self.curve.tangent_at(t).ccw_perpendicular();
+        let deriv = self.stretch(t).map_or(Vec2::zero(), |s| s * self.d1.at(t));
+        (pos, deriv)
+    }
+
+    // The parameters where the stretch changes sign, found by sampling and bisection
+    fn cusps(&self) -> Vec<Coord> {
+        let mut cusps = Vec::new();
+        let samples: Vec<_> = (0..=OFFSET_CUSP_SAMPLES)
+            .map(|i| i as Coord / OFFSET_CUSP_SAMPLES as Coord)
+            .filter_map(|t| self.stretch(t).map(|s| (t, s))).collect();
+
+        for w in samples.windows(2) {
+            let ((mut l, sl), (mut r, sr)) = (w[0], w[1]);
+            if sl.signum() == sr.signum() { continue; }
+
+            for _ in 0..OFFSET_CUSP_ITERATIONS {
+                let m = (l + r) / 2.0;
+                match self.stretch(m) {
+                    Some(s) if s.signum() == sl.signum() => l = m,
+                    _ => r = m
+                }
+            }
+            cusps.push((l + r) / 2.0);
+        }
+
+        cusps
+    }
+
+    // Approximate the offset between l and r by cubics that interpolate its endpoints and
+    // derivatives, splitting it until the cubics are close enough
+    fn approximate(&self, out: &mut Vec<Curve>, l: Coord, r: Coord, tolerance: Coord, depth: usize) {
+        let h = r - l;
+        let (p0, v0) = self.at(l);
+        let (p3, v3) = self.at(r);
+        let cubic = Curve::cubic_bezier(p0, p0 + v0 * h / 3.0, p3 - v3 * h / 3.0, p3);
+
+        let error = [0.25, 0.5, 0.75].iter()
+            .map(|&s| (cubic.at(s) - self.at(l + s * h).0).length())
+            .fold(0.0, Coord::max);
+
+        if error <= tolerance || depth >= MAX_OFFSET_DEPTH { out.push(cubic); }
+        else {
+            let m = (l + r) / 2.0;
+            self.approximate(out, l, m, tolerance, depth + 1);
+            self.approximate(out, m, r, tolerance, depth + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The distance from a point to the curve, by dense sampling
+    fn distance_to(curve: &Curve, p: Vec2) -> Coord {
+        (0..=2048).map(|i| (curve.at(i as Coord / 2048.0) - p).length()).fold(Coord::INFINITY, Coord::min)
+    }
+
+    #[test]
+    fn test_exact_offsets() {
+        let line = Curve::line(Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0));
+        let offset = line.offset(0.5, 1e-3);
+        assert_eq!(offset.len(), 1);
+        assert!(offset[0].at(0.0).roughly_equals(Vec2::new(0.0, 0.5)));
+        assert!(offset[0].at(1.0).roughly_equals(Vec2::new(2.0, 0.5)));
+
+        // A quarter circle of radius 2 around the origin, going counterclockwise (in math space)
+        let arc = Curve::circle(Vec2::zero(), 2.0, Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0), false);
+        let offset = arc.offset(0.5, 1e-3);
+        assert!(offset[0].at(0.0).roughly_equals(Vec2::new(1.5, 0.0)));
+        assert!(offset[0].at(1.0).roughly_equals(Vec2::new(0.0, 1.5)));
+
+        // Going past the center puts the offset on the other side
+        let offset = arc.offset(3.0, 1e-3);
+        assert!(offset[0].at(0.0).roughly_equals(Vec2::new(-1.0, 0.0)));
+        assert!(offset[0].at(1.0).roughly_equals(Vec2::new(0.0, -1.0)));
+        assert!(offset[0].at(0.5).roughly_equals(arc.at(0.5) * -0.5));
+    }
+
+    #[test]
+    fn test_approximate_offsets() {
+        let curves = [
+            Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0), Vec2::new(4.0, 0.0)),
+            Curve::cubic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(3.0, -2.0), Vec2::new(4.0, 0.0)),
+            Curve::elliptic_arc(Vec2::new(2.0, 0.0), Vec2::new(2.0, 1.0), 0.0, false, true, Vec2::new(-2.0, 0.0))
+        ];
+
+        for curve in curves.iter() {
+            for &distance in [0.25, -0.25].iter() {
+                let offset = curve.offset(distance, 1e-3);
+                assert!(offset[0].at(0.0).roughly_equals(curve.at(0.0) + distance * curve.entry_tangent().ccw_perpendicular()));
+                for piece in &offset {
+                    for i in 0..=8 {
+                        let p = piece.at(i as Coord / 8.0);
+                        assert!((distance_to(curve, p) - distance.abs()).abs() < 2e-3, "{} at {}", curve, distance);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_offset_cusps() {
+        // The apex of the parabola has radius of curvature 0.5, so offsetting it inwards by more
+        // than that creates two cusps
+        let curve = Curve::quadratic_bezier(Vec2::new(-2.0, 4.0), Vec2::new(0.0, -4.0), Vec2::new(2.0, 4.0));
+        assert_eq!(OffsetCurve::new(&curve, 1.0).cusps().len(), 2);
+        assert!(OffsetCurve::new(&curve, 0.25).cusps().is_empty());
+        assert!(OffsetCurve::new(&curve, -1.0).cusps().is_empty());
+        assert!(curve.offset(1.0, 1e-3).len() >= 3);
+    }
+}
@@ -0,0 +1,86 @@
+//--------------------------------------------------------------------
+// projection.rs
+//--------------------------------------------------------------------
+// Provides the projection of points onto the curves, and the distances
+// derived from it
+//--------------------------------------------------------------------
+
+use crate::geometry::*;
+use super::*;
+
+// The number of parameter intervals sampled to seed the refinement, and how many refinement
+// steps each seed takes
+const PROJECTION_SAMPLES: usize = 16;
+const PROJECTION_ITERATIONS: usize = 8;
+
+impl Curve {
+    // The parameter and the point of the curve closest to p; the curve is sampled to find the
+    // candidates, which are then refined with Newton's method on (c(t) - p) · c'(t) = 0
+    pub fn project_point(&self, p: Vec2) -> (Coord, Vec2) {
+        if let Curve::Line(l) = self {
+            let d = l.b - l.a;
+            let t = if d.roughly_zero() { 0.0 } else { ((p - l.a).dot(d) / d.length_sq()).clamp(0.0, 1.0) };
+            return (t, self.at(t));
+        }
+
+        let d1 = self.derivative();
+        let d2 = d1.derivative();
+
+        let mut best = (0.0, self.at(0.0));
+        for i in 0..=PROJECTION_SAMPLES {
+            let mut t = i as Coord / PROJECTION_SAMPLES as Coord;
+            for _ in 0..PROJECTION_ITERATIONS {
+                let (v, dv) = (self.at(t) - p, d1.at(t));
+                let slope = dv.length_sq() + v.dot(d2.at(t));
+                if slope.roughly_zero_squared() { break; }
+                t = (t - v.dot(dv) / slope).clamp(0.0, 1.0);
+            }
+
+            let q = self.at(t);
+            if (q - p).length_sq() < (best.1 - p).length_sq() { best = (t, q); }
+        }
+        best
+    }
+
+    // The distance from p to the closest point of the curve
+    pub fn distance_to(&self, p: Vec2) -> Coord { (self.project_point(p).1 - p).length() }
+
+    // The same distance, positive if p is on the left of the curve (where its normal, the tangent
+    // rotated counterclockwise, points) and negative if on the right, so the inside of the
+    // counterclockwise contours is on the positive side
+    pub fn signed_distance_to(&self, p: Vec2) -> Coord {
+        let (t, q) = self.project_point(p);
+        let distance = (p - q).length();
+        if self.tangent_at(t).cross(p - q) < 0.0 { -distance } else { distance }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_distance_to() {
+        let line = Curve::line(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+        assert!(line.distance_to(Vec2::new(1.0, 2.0)).roughly_equals(2.0));
+        assert!(line.distance_to(Vec2::new(7.0, 4.0)).roughly_equals(5.0));
+        assert!(line.signed_distance_to(Vec2::new(1.0, 2.0)).roughly_equals(2.0));
+        assert!(line.signed_distance_to(Vec2::new(1.0, -2.0)).roughly_equals(-2.0));
+
+        // A counterclockwise semicircle has its center on its left
+        let center = Vec2::new(1.0, 1.0);
+        let arc = Curve::circle(center, 2.0, Vec2::new(1.0, 0.0), Vec2::new(-1.0, 0.0), false);
+        assert!(arc.signed_distance_to(center + Vec2::new(0.5, 0.5)).roughly_equals(2.0 - 0.5 * CoordM::consts::SQRT_2));
+        assert!(arc.signed_distance_to(center + Vec2::new(0.0, 3.0)).roughly_equals(-1.0));
+        assert!(arc.reverse().signed_distance_to(center + Vec2::new(0.0, 3.0)).roughly_equals(1.0));
+
+        // The closest point of a cubic satisfies the orthogonality condition
+        let cubic = Curve::cubic_bezier(Vec2::new(0.0, 0.0), Vec2::new(1.0, 3.0), Vec2::new(3.0, -2.0), Vec2::new(4.0, 1.0));
+        let p = Vec2::new(2.0, 2.0);
+        let (t, q) = cubic.project_point(p);
+        assert!((q - p).dot(cubic.tangent_at(t)).roughly_zero());
+        for i in 0..=100 {
+            assert!((cubic.at(i as Coord / 100.0) - p).length() >= cubic.distance_to(p) - EPSILON);
+        }
+    }
+}
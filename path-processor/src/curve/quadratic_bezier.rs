@@ -8,12 +8,13 @@ use crate::derive_more::*;
 
 use crate::geometry::*;
 use super::line::Line;
+use super::cubic_bezier::CubicBezier;
 use crate::arrayvec::*;
 use super::*;
 use crate::vec_utils::*;
 
 // The quadratic bezier structure
-#[derive(Display)]
+#[derive(Copy, Clone, Display)]
 #[display(fmt = "QuadraticBezier({},{},{})", a, b, c)]
 pub struct QuadraticBezier { pub a: Vec2, pub b: Vec2, pub c: Vec2 }
 
@@ -41,21 +42,34 @@ impl QuadraticBezier {
 
     pub fn reverse(&self) -> QuadraticBezier { QuadraticBezier { a: self.c, b: self.b, c: self.a } }
 
+    pub fn mirror(&self, axis_point: Vec2, axis_dir: Vec2) -> QuadraticBezier {
+        let r = |v: Vec2| v.reflect(axis_point, axis_dir);
+        QuadraticBezier { a: r(self.a), b: r(self.b), c: r(self.c) }
+    }
+
+    // Exact degree elevation to a cubic Bézier
+    pub fn elevate(&self) -> CubicBezier {
+        let b = self.a + 2.0 / 3.0 * (self.b - self.a);
+        let c = self.c + 2.0 / 3.0 * (self.b - self.c);
+        CubicBezier { a: self.a, b, c, d: self.c }
+    }
+
     pub fn winding(&self) -> Coord { 
         (2.0 * self.a.cross(self.b) + 2.0 * self.b.cross(self.c) + self.a.cross(self.c)) / 3.0
     }
 
+    #[cfg(feature = "std")]
     pub fn angle_key(&self) -> AngleKey {
         let dv1 = self.b - self.a;
         let dv2 = self.c - self.b;
 
         // If dv1 is zero the following angles will fall apart, so we take the limit
-        if dv1.roughly_zero() { self.derivative().angle_key() }
-        else {
-            let dt = dv1.cross(dv2 - dv1) / dv1.length_sq();
-            let ddt = -2.0 * dv1.dot(dv2 - dv1) * dt / dv1.length_sq();
-            AngleKey::new(dv1.angle(), dt, ddt)
+        // (and if dv2 is also zero, there is no limit to take, so use the chord)
+        if dv1.roughly_zero() {
+            if dv2.roughly_zero() { AngleKey::from_chord(self.a, self.c) }
+            else { self.derivative().angle_key() }
         }
+        else { AngleKey::from_derivatives(dv1, dv2 - dv1, Vec2::zero(), Vec2::zero()) }
     }
 
     pub fn intersection_x(&self, x: Coord) -> roots::Roots<Coord> {
@@ -80,6 +94,15 @@ impl QuadraticBezier {
         [self.a, self.b, self.c].into_iter().copied().collect()
     }
 
+    // The exact bounding box: the extrema of each coordinate are where its derivative vanishes, and
+    // the endpoints are always included, even if those parameters are lost to rounding
+    pub fn tight_bbox(&self) -> Rect {
+        let d = self.derivative();
+        let ts = [d.a.x / (d.a.x - d.b.x), d.a.y / (d.a.y - d.b.y)];
+        let extrema = ts.iter().filter(|&&t| t > 0.0 && t < 1.0).map(|&t| self.at(t));
+        Rect::enclosing_rect([self.a, self.c].iter().copied().chain(extrema)).unwrap()
+    }
+
     pub fn critical_points(&self) -> ArrayVec<[Coord; MAX_CRITICAL_POINTS]> {
         let dd = self.derivative();
         let tx = dd.a.x / (dd.a.x - dd.b.x);
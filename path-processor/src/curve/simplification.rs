@@ -19,6 +19,26 @@ pub fn simplify_curves(curves: Vec<Curve>) -> Vec<Curve> {
     new_curves
 }
 
+// Fuse the runs of adjacent lines that go in the same direction into single lines
+pub fn merge_collinear_lines(curves: Vec<Curve>) -> Vec<Curve> {
+    let mut new_curves: Vec<Curve> = Vec::with_capacity(curves.len());
+    for curve in curves {
+        if let (Some(Curve::Line(l1)), Curve::Line(l2)) = (new_curves.last_mut(), &curve) {
+            let d1 = (l1.b - l1.a).normalized();
+            let d2 = (l2.b - l2.a).normalized();
+
+            // Lines going back on themselves are not merged, since they would cancel out
+            if l1.b.roughly_equals(l2.a) && d1.dot(d2) > 0.0 && d1.cross(d2).roughly_zero_squared() {
+                l1.b = l2.b;
+                continue;
+            }
+        }
+
+        new_curves.push(curve);
+    }
+    new_curves
+}
+
 pub fn is_curve_degenerate(curve: &Curve) -> bool {
     match curve {
         Curve::Line(l) => l.a.roughly_equals(l.b),
@@ -76,50 +96,15 @@ fn simplify_cubic_bezier(out: &mut Vec<Curve>, c: &cubic_bezier::CubicBezier) {
         let mut vec: Vec<_> = tmp.filter(|&&t| inside01(t)).cloned().collect();
         vec.sort_by(|a, b| a.partial_cmp(b).unwrap());
         for i in 1..vec.len() { out.push(Curve::line(c.at(vec[i-1]), c.at(vec[i]))) }
-    } else if (c.a - 3.0 * c.b + 3.0 * c.c - c.d).roughly_zero() {
+    } else if let Some(q) = c.try_reduce() {
         // The Bézier should be a quadratic instead
-        let b1 = 3.0 * c.b - c.a;
-        let b2 = 3.0 * c.c - c.d;
-
-        // Subdivide as if it were a quadratic
-        out.push(Curve::quadratic_bezier(c.a, (b1 + b2) / 4.0, c.c));
+        out.push(Curve::QuadraticBezier(q));
     } else {
         // Detect loops, cusps and inflection points
         let mut roots = vec![0.0, 1.0];
 
-        // Here we use the method based on https://stackoverflow.com/a/38644407
-        let mut da = c.b - c.a;
-        let mut db = c.c - c.b;
-        let mut dc = c.d - c.c;
-
-        // Check for no loops on cross product
-        let ab = da.cross(db);
-        let ac = da.cross(dc);
-        let bc = db.cross(dc);
-        if ac * ac <= 4.0 * ab * bc {
-            // The coefficients of the canonical form
-            let mut c3 = da + dc - 2.0 * db;
-
-            // Rotate if beforehand if necessary
-            if !c3.y.roughly_zero() {
-                c3 = Vec2::new(c3.x, -c3.y);
-                da = da.rot_scale(c3);
-                db = db.rot_scale(c3);
-                dc = dc.rot_scale(c3);
-                c3 = da + dc - 2.0 * db;
-            }
-
-            let c2 = 3.0 * (db - da);
-            let c1 = 3.0 * da;
-
-            // Calculate the coefficients of the loop polynomial
-            let bb = -c1.y / c2.y;
-            let s1 = c1.x / c3.x;
-            let s2 = c2.x / c3.x;
-
-            // Find the roots (that happen to be the loop points)
-            roots.extend_from_slice(roots::find_roots_quadratic(1.0, -bb, bb * (bb + s2) + s1).as_ref());
-        }
+        // Split on the self-intersection, so each piece is simple
+        roots.extend_from_slice(&c.loop_parameters());
 
         // The inflection point polynom
         let axby = c.a.x * c.b.y; let axcy = c.a.x * c.c.y; let axdy = c.a.x * c.d.y;
@@ -158,5 +143,54 @@ fn simplify_elliptic_arc(out: &mut Vec<Curve>, a: &elliptic_arc::EllipticArc) {
         tests.sort_by(|a, b| a.partial_cmp(b).unwrap());
         for i in 1..tests.len() { out.push(Curve::line(a.at(tests[i-1]), a.at(tests[i]))); }
     }
-    else { out.push(Curve::EllipticArc(elliptic_arc::EllipticArc { ..*a })); }
+    else { out.extend(a.split_full_turns().map(Curve::EllipticArc)); }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_merge_collinear_lines() {
+        let pts = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0), Vec2::new(3.0, 3.0),
+            Vec2::new(3.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(2.0, 0.0)];
+        let curves = (1..pts.len()).map(|i| Curve::line(pts[i-1], pts[i])).collect();
+        let merged = merge_collinear_lines(curves);
+
+        // The diagonal run gets merged, but neither the corner nor the backtracking line
+        let expected = [(0, 3), (3, 4), (4, 5), (5, 6)];
+        assert_eq!(merged.len(), expected.len());
+        for (c, &(i, j)) in merged.iter().zip(expected.iter()) {
+            assert!(c.roughly_equals(&Curve::line(pts[i], pts[j])), "{}", c);
+        }
+    }
+
+    #[test]
+    fn test_full_circle_split() {
+        let circle = elliptic_arc::EllipticArc { center: Vec2::new(1.0, 1.0), radii: Vec2::new(2.0, 1.0),
+            crot: Vec2::new(1.0, 0.0), t1: 0.5, dt: 2.0 * CoordM::consts::PI };
+
+        let curves = simplify_curves(vec![Curve::EllipticArc(circle)]);
+        assert_eq!(curves.len(), 2);
+        assert!(curves[0].at(0.0).roughly_equals(circle.at(0.0)));
+        assert!(curves[0].at(1.0).roughly_equals(curves[1].at(0.0)));
+        assert!(curves[1].at(1.0).roughly_equals(circle.at(0.0)));
+        assert!(curves[0].at(1.0).roughly_equals(circle.at(0.5)));
+    }
+
+    #[test]
+    fn test_loop_split() {
+        let looping = cubic_bezier::CubicBezier { a: Vec2::new(0.0, 0.0), b: Vec2::new(4.0, 1.0),
+            c: Vec2::new(-1.0, 1.0), d: Vec2::new(3.0, 0.0) };
+        let (t1, t2) = looping.self_intersection().unwrap();
+
+        // The double point must be an endpoint of the pieces, which then only meet at their ends
+        let curves = simplify_curves(vec![Curve::CubicBezier(looping)]);
+        let double_point = looping.at(t1);
+        assert!(curves.iter().filter(|c| c.at(1.0).roughly_equals(double_point)).count() >= 2);
+        assert!(looping.at(t2).roughly_equals(double_point));
+        for c in &curves {
+            if let Curve::CubicBezier(c) = c { assert!(c.self_intersection().is_none()); }
+        }
+    }
 }
@@ -24,6 +24,12 @@ pub trait Geometry where Self: Sized {
     fn roughly_zero_squared(self) -> bool;
     fn roughly_equals(self, other: Self) -> bool;
     fn roughly_equals_squared(self, other: Self) -> bool;
+
+    // The same comparisons with an explicit tolerance; the squared ones compare against eps²
+    fn roughly_zero_eps(self, eps: Self) -> bool;
+    fn roughly_zero_squared_eps(self, eps: Self) -> bool;
+    fn roughly_equals_eps(self, other: Self, eps: Self) -> bool;
+    fn roughly_equals_squared_eps(self, other: Self, eps: Self) -> bool;
 }
 
 impl Geometry for Coord {
@@ -36,10 +42,15 @@ impl Geometry for Coord {
     fn wrap_angle_360_ccw(self) -> Coord { self - TWO_PI * (self / TWO_PI).ceil() }
     fn wrap_angle_360_cw(self) -> Coord { self - TWO_PI * (self / TWO_PI).floor() }
 
-    fn roughly_zero(self) -> bool { self > -EPSILON && self < EPSILON }
-    fn roughly_zero_squared(self) -> bool { self > -EPSILON2 && self < EPSILON2 }
-    fn roughly_equals(self, other: Self) -> bool { (self - other).roughly_zero() }
-    fn roughly_equals_squared(self, other: Self) -> bool { (self - other).roughly_zero_squared() }
+    fn roughly_zero(self) -> bool { self.roughly_zero_eps(EPSILON) }
+    fn roughly_zero_squared(self) -> bool { self.roughly_zero_squared_eps(EPSILON) }
+    fn roughly_equals(self, other: Self) -> bool { self.roughly_equals_eps(other, EPSILON) }
+    fn roughly_equals_squared(self, other: Self) -> bool { self.roughly_equals_squared_eps(other, EPSILON) }
+
+    fn roughly_zero_eps(self, eps: Coord) -> bool { self > -eps && self < eps }
+    fn roughly_zero_squared_eps(self, eps: Coord) -> bool { self.roughly_zero_eps(eps * eps) }
+    fn roughly_equals_eps(self, other: Coord, eps: Coord) -> bool { (self - other).roughly_zero_eps(eps) }
+    fn roughly_equals_squared_eps(self, other: Coord, eps: Coord) -> bool { (self - other).roughly_zero_squared_eps(eps) }
 }
 
 pub fn inside01(t: Coord) -> bool { t >= 0.0 && t <= 1.0 }
@@ -0,0 +1,39 @@
+//--------------------------------------------------------------------
+// float.rs
+//--------------------------------------------------------------------
+// Provides the float methods that come from std, implemented with libm,
+// for the builds without std
+//--------------------------------------------------------------------
+
+use super::Coord;
+
+// The methods have the same names as std's, so the geometry code calls them the same way
+pub trait FloatExt: Sized {
+    fn sqrt(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn acos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn cbrt(self) -> Self;
+    fn round(self) -> Self;
+    fn ceil(self) -> Self;
+    fn floor(self) -> Self;
+}
+
+impl FloatExt for Coord {
+    fn sqrt(self) -> Coord { libm::sqrt(self) }
+    fn powi(self, n: i32) -> Coord { libm::pow(self, n as Coord) }
+    fn sin(self) -> Coord { libm::sin(self) }
+    fn cos(self) -> Coord { libm::cos(self) }
+    fn tan(self) -> Coord { libm::tan(self) }
+    fn sin_cos(self) -> (Coord, Coord) { libm::sincos(self) }
+    fn acos(self) -> Coord { libm::acos(self) }
+    fn atan2(self, other: Coord) -> Coord { libm::atan2(self, other) }
+    fn cbrt(self) -> Coord { libm::cbrt(self) }
+    fn round(self) -> Coord { libm::round(self) }
+    fn ceil(self) -> Coord { libm::ceil(self) }
+    fn floor(self) -> Coord { libm::floor(self) }
+}
@@ -9,9 +9,14 @@ mod rect;
 mod vec2;
 mod vec4;
 mod polygon;
+#[cfg(not(feature = "std"))]
+mod float;
 
 pub type Coord = f64;
-pub use std::f64 as CoordM;
+pub use core::f64 as CoordM;
+
+#[cfg(not(feature = "std"))]
+pub use float::FloatExt;
 
 pub use coord_utils::*;
 pub use rect::*;
@@ -5,6 +5,8 @@
 //--------------------------------------------------------------------
 
 use crate::geometry::*;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
 
 fn inside_segment_collinear(x0: Vec2, x1: Vec2, y: Vec2, strict: bool) -> bool {
     let d = (x1 - x0).dot(y - x0);
@@ -12,7 +14,8 @@ fn inside_segment_collinear(x0: Vec2, x1: Vec2, y: Vec2, strict: bool) -> bool {
     else { d >= 0.0 && d <= (x1 - x0).length_sq() }
 }
 
-fn segments_intersect(p0: Vec2, p1: Vec2, q0: Vec2, q1: Vec2, strict: bool) -> bool {
+// Check whether two segments meet; if strict, segments that only touch don't count
+pub fn segments_intersect(p0: Vec2, p1: Vec2, q0: Vec2, q1: Vec2, strict: bool) -> bool {
     // The cross products
     let crossq0 = (p1 - p0).cross(q0 - p0);
     let crossq1 = (p1 - p0).cross(q1 - p0);
@@ -51,6 +54,7 @@ fn segments_intersect(p0: Vec2, p1: Vec2, q0: Vec2, q1: Vec2, strict: bool) -> b
     true
 }
 
+// Twice the signed area of the polygon, positive if it is counterclockwise
 pub fn polygon_winding(poly: &[Vec2]) -> Coord {
     let mut winding = 0.0;
 
@@ -80,7 +84,9 @@ pub fn segment_equivalent(poly: &[Vec2]) -> Option<(Vec2, Vec2)> {
     }
 }
 
-fn polygon_contains_point(poly: &[Vec2], p: Vec2, strict: bool) -> bool {
+// Check whether the point is inside the polygon (by the even-odd rule); if strict, points on the
+// boundary don't count
+pub fn polygon_contains_point(poly: &[Vec2], p: Vec2, strict: bool) -> bool {
     let mut contains = false;
 
     for i in 0..poly.len() {
@@ -120,6 +126,7 @@ fn polygon_segment_intersect(poly: &[Vec2], a: Vec2, b: Vec2, strict: bool) -> b
     false
 }
 
+// Check whether two polygons overlap; if strict, polygons that only touch don't count
 pub fn polygons_overlap(poly0: &[Vec2], poly1: &[Vec2], strict: bool) -> bool {
     // Check first for segment polygons
     let s0 = segment_equivalent(poly0);
@@ -154,10 +161,17 @@ pub fn polygons_overlap(poly0: &[Vec2], poly1: &[Vec2], strict: bool) -> bool {
     false
 }
 
-pub fn convex_hull(mut points: Vec<Vec2>) -> Vec<Vec2> {
-    // Sort the points using the canonical comparer
+// Sort the points using the canonical comparer and remove the ones that are roughly equal to
+// their predecessors, so near duplicates coming from floating-point error are dropped too
+pub fn dedup_points(points: &mut Vec<Vec2>) {
     points.sort_by(canonical);
-    points.dedup();
+    points.dedup_by(|p, prev| p.roughly_equals(*prev));
+}
+
+// The convex hull of the points, in counterclockwise order
+pub fn convex_hull(mut points: Vec<Vec2>) -> Vec<Vec2> {
+    dedup_points(&mut points);
+    if points.len() < 3 { return points; }
 
     let mut hull = Vec::with_capacity(points.len() + 1);
     // Work with the points array forwards and backwards
@@ -188,6 +202,7 @@ pub fn convex_hull(mut points: Vec<Vec2>) -> Vec<Vec2> {
     hull
 }
 
+// Remove the repeated and collinear vertices of the polygon
 pub fn simplify_polygon(poly: &[Vec2]) -> Vec<Vec2> {
     // Quickly discard degenerate polygons
     if poly.len() < 3 { return poly.iter().copied().collect(); }
@@ -234,3 +249,43 @@ pub fn simplify_polygon(poly: &[Vec2]) -> Vec<Vec2> {
         pts
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_convex_hull() {
+        let points = vec![Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 2.0), Vec2::new(0.0, 2.0), Vec2::new(1.0, 0.0)];
+        let hull = convex_hull(points);
+        assert_eq!(hull.len(), 4);
+        assert!(polygon_winding(&hull).roughly_equals(8.0));
+
+        // Inputs too small to have a hull are returned as they are
+        assert_eq!(convex_hull(vec![Vec2::new(1.0, 1.0); 3]).len(), 1);
+        assert!(convex_hull(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_dedup_points() {
+        let mut points = vec![Vec2::new(1.0, 1.0), Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0 + EPSILON / 4.0),
+            Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0)];
+        dedup_points(&mut points);
+        assert_eq!(points.len(), 3);
+
+        // The near duplicate must not create a spurious vertex on the hull
+        let hull = convex_hull(vec![Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 2.0),
+            Vec2::new(2.0 + EPSILON / 4.0, 2.0 - EPSILON / 4.0), Vec2::new(0.0, 2.0)]);
+        assert_eq!(hull.len(), 4);
+    }
+
+    #[test]
+    fn test_polygons_overlap() {
+        let square = |x: Coord, y: Coord| Rect::new(x, y, 1.0, 1.0).vertices();
+        assert!(polygons_overlap(&square(0.0, 0.0), &square(0.5, 0.5), true));
+        assert!(polygons_overlap(&square(0.0, 0.0), &square(1.0, 0.5), false));
+        assert!(!polygons_overlap(&square(0.0, 0.0), &square(1.0, 0.5), true));
+        assert!(!polygons_overlap(&square(0.0, 0.0), &square(2.0, 0.0), false));
+    }
+}
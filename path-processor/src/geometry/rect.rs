@@ -7,7 +7,7 @@
 use crate::derive_more::*;
 
 use super::*;
-use std::iter::Iterator;
+use core::iter::Iterator;
 use CoordM::INFINITY;
 
 #[derive(Copy, Clone, Mul, Div, MulAssign, DivAssign, Debug, Display, Constructor)]
@@ -15,6 +15,22 @@ use CoordM::INFINITY;
 pub struct Rect { pub x: Coord, pub y: Coord, pub width: Coord, pub height: Coord }
 
 impl Rect {
+    // Build the rectangle from its minimum and maximum corners
+    pub fn from_corners(min: Vec2, max: Vec2) -> Rect {
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    // The minimum and maximum corners of the rectangle
+    pub fn corners(&self) -> (Vec2, Vec2) {
+        (Vec2::new(self.x, self.y), Vec2::new(self.x + self.width, self.y + self.height))
+    }
+
+    // The four corners of the rectangle, starting at the minimum one and going through (x + width, y)
+    pub fn vertices(&self) -> [Vec2; 4] {
+        let (min, max) = self.corners();
+        [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)]
+    }
+
     pub fn intersects(&self, other: Rect) -> bool {
         !(self.x > other.x + other.width || other.x > self.x + self.width ||
             self.y > other.y + other.height || other.y > self.y + self.height)
@@ -49,10 +65,26 @@ impl Rect {
         }
     }
 
+    // The smallest rectangle that contains both rectangles
+    pub fn union(&self, other: Rect) -> Rect {
+        let x1 = self.x.min(other.x);
+        let x2 = (self.x + self.width).max(other.x + other.width);
+        let y1 = self.y.min(other.y);
+        let y2 = (self.y + self.height).max(other.y + other.height);
+
+        Rect::new(x1, y1, x2 - x1, y2 - y1)
+    }
+
     pub fn contains_point(&self, pt: Vec2) -> bool {
         self.x <= pt.x && self.y <= pt.y && self.x + self.width >= pt.x && self.y + self.height >= pt.y
     }
 
+    // The point inside the rectangle closest to the given point
+    pub fn clamp_point(&self, pt: Vec2) -> Vec2 {
+        let (min, max) = self.corners();
+        pt.clamp(min, max)
+    }
+
     pub fn enclosing_rect(pts: impl Iterator<Item = Vec2>) -> Option<Rect> {
         let mut x1 = INFINITY;
         let mut x2 = -INFINITY;
@@ -79,3 +111,36 @@ impl Rect {
         Rect::new(x1, y1, x2 - x1, y2 - y1)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_corners_round_trip() {
+        let rect = Rect::from_corners(Vec2::new(-1.0, 2.0), Vec2::new(3.0, 5.0));
+        assert_eq!((rect.x, rect.y, rect.width, rect.height), (-1.0, 2.0, 4.0, 3.0));
+
+        let (min, max) = rect.corners();
+        assert!(min.roughly_equals(Vec2::new(-1.0, 2.0)));
+        assert!(max.roughly_equals(Vec2::new(3.0, 5.0)));
+    }
+
+    #[test]
+    fn test_clamp_point() {
+        let rect = Rect::new(1.0, 2.0, 4.0, 3.0);
+        let cases = [
+            // Inside, and outside each edge
+            ((2.0, 3.0), (2.0, 3.0)), ((-1.0, 3.0), (1.0, 3.0)), ((7.0, 4.0), (5.0, 4.0)),
+            ((3.0, 0.0), (3.0, 2.0)), ((3.0, 9.0), (3.0, 5.0)),
+            // Outside each corner
+            ((0.0, 0.0), (1.0, 2.0)), ((6.0, 1.0), (5.0, 2.0)), ((6.0, 6.0), (5.0, 5.0)), ((-3.0, 8.0), (1.0, 5.0))
+        ];
+
+        for &((x, y), (cx, cy)) in &cases {
+            let clamped = rect.clamp_point(Vec2::new(x, y));
+            assert_eq!(clamped, Vec2::new(cx, cy));
+            assert!(rect.contains_point(clamped));
+        }
+    }
+}
@@ -7,7 +7,8 @@
 use crate::derive_more::*;
 
 use super::*;
-use std::cmp::Ordering;
+use core::cmp::Ordering;
+use CoordM::consts::PI;
 
 // Vec2
 #[derive(Copy, Clone, Add, Sub, Mul, Div, AddAssign, SubAssign, Neg, PartialEq,
@@ -37,12 +38,46 @@ impl Vec2 {
     pub fn ccw_perpendicular(&self) -> Vec2 { Vec2 { x: -self.y, y: self.x } }
     pub fn cw_perpendicular(&self) -> Vec2 { -self.ccw_perpendicular() }
 
+    // Reflect the point across the line that passes through axis_point with direction axis_dir
+    pub fn reflect(&self, axis_point: Vec2, axis_dir: Vec2) -> Vec2 {
+        let dir = axis_dir.normalized();
+        let v = *self - axis_point;
+        axis_point + 2.0 * v.dot(dir) * dir - v
+    }
+
     pub fn angle(&self) -> Coord { self.y.atan2(self.x) }
     pub fn angle_facing(&self, other: Vec2) -> Coord { (other - *self).angle() }
+    // The CCW rotation from self to other, in [-π, π] (-π is possible because of the signed zero)
     pub fn angle_between(&self, other: Vec2) -> Coord { self.cross(other).atan2(self.dot(other)) }
 
-    pub fn roughly_zero(&self) -> bool { self.length_sq().roughly_zero_squared() }
-    pub fn roughly_equals(&self, other: Vec2) -> bool { (*self - other).length_sq().roughly_zero_squared() } 
+    // The CCW rotation from self to other, guaranteed to be in (-π, π]
+    pub fn signed_angle_to(&self, other: Vec2) -> Coord {
+        let angle = self.angle_between(other);
+        if angle <= -PI { PI } else { angle }
+    }
+
+    // The angle between self and other regardless of direction, in [0, π]
+    pub fn unsigned_angle_to(&self, other: Vec2) -> Coord { self.angle_between(other).abs() }
+
+    // Round both coordinates to the nearest multiples of grid
+    pub fn snap(&self, grid: Coord) -> Vec2 { Vec2::new((self.x / grid).round() * grid, (self.y / grid).round() * grid) }
+
+    // Clamp each coordinate to the range given by min and max
+    pub fn clamp(&self, min: Vec2, max: Vec2) -> Vec2 {
+        Vec2::new(self.x.max(min.x).min(max.x), self.y.max(min.y).min(max.y))
+    }
+
+    pub fn is_finite(&self) -> bool { self.x.is_finite() && self.y.is_finite() }
+
+    // The canonical sweep order, as a method
+    pub fn cmp_canonical(&self, other: &Vec2) -> Ordering { canonical(self, other) }
+
+    pub fn roughly_zero(&self) -> bool { self.roughly_zero_eps(EPSILON) }
+    pub fn roughly_equals(&self, other: Vec2) -> bool { self.roughly_equals_eps(other, EPSILON) }
+
+    // Whether the vector is shorter than eps, or the points are closer than eps
+    pub fn roughly_zero_eps(&self, eps: Coord) -> bool { self.length_sq().roughly_zero_squared_eps(eps) }
+    pub fn roughly_equals_eps(&self, other: Vec2, eps: Coord) -> bool { (*self - other).roughly_zero_eps(eps) }
 }
 
 // Implement scalar * mul as required
@@ -51,14 +86,65 @@ impl core::ops::Mul<Vec2> for Coord {
     fn mul(self, rhs: Vec2) -> Vec2 { Vec2::new(self * rhs.x, self * rhs.y) }
 }
 
+// The sweep order used by the triangulation and the DCEL: points go by increasing y (top to bottom
+// in a y-down space), and points on the same row by decreasing x; sweep-line algorithms built on
+// this crate should use it instead of their own order. Panics on NaN coordinates
 pub fn canonical(a: &Vec2, b: &Vec2) -> Ordering {
     if a.y == b.y { b.x.partial_cmp(&a.x).unwrap() }
     else { a.y.partial_cmp(&b.y).unwrap() }
 }
 
-impl std::fmt::Debug for Vec2 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for Vec2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self)
     }
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[test]
+    fn test_explicit_epsilon() {
+        let a = Vec2::new(1.0, 1.0);
+        let b = Vec2::new(1.0, 1.01);
+        assert!(!a.roughly_equals(b));
+        assert!(a.roughly_equals_eps(b, 0.1));
+        assert!(!a.roughly_equals_eps(b, 0.001));
+        assert!((b - a).roughly_zero_eps(0.1));
+
+        assert!(0.01.roughly_zero_eps(0.1) && !0.01.roughly_zero());
+        assert!(1e-3.roughly_zero_squared_eps(0.1) && !0.1.roughly_zero_squared_eps(0.1));
+        assert!(1.0.roughly_equals_eps(1.05, 0.1));
+    }
+
+    #[test]
+    fn test_canonical_order() {
+        let mut points = vec![Vec2::new(0.0, 1.0), Vec2::new(2.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(-1.0, 0.0)];
+        points.sort_by(canonical);
+        assert_eq!(points, vec![Vec2::new(2.0, 0.0), Vec2::new(-1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)]);
+        assert_eq!(points[0].cmp_canonical(&points[1]), Ordering::Less);
+        assert_eq!(points[2].cmp_canonical(&points[2]), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_angle_conventions() {
+        let x = Vec2::new(1.0, 0.0);
+        let y = Vec2::new(0.0, 1.0);
+
+        // Counter-clockwise rotations are positive
+        assert!(x.angle_between(y).roughly_equals(PI / 2.0));
+        assert!(y.angle_between(x).roughly_equals(-PI / 2.0));
+        assert!(x.signed_angle_to(y).roughly_equals(PI / 2.0));
+        assert!(y.signed_angle_to(x).roughly_equals(-PI / 2.0));
+        assert!(y.unsigned_angle_to(x).roughly_equals(PI / 2.0));
+
+        // Opposite vectors give π, never -π, even with the signed zero
+        assert_eq!(x.signed_angle_to(-x), PI);
+        assert_eq!(x.signed_angle_to(Vec2::new(-1.0, -0.0)), PI);
+        assert_eq!(Vec2::new(1.0, -0.0).signed_angle_to(-x), PI);
+        assert_eq!(x.unsigned_angle_to(Vec2::new(-1.0, -0.0)), PI);
+    }
+}
\ No newline at end of file
@@ -30,8 +30,8 @@ impl core::ops::Mul<Vec4> for Coord {
     fn mul(self, rhs: Vec4) -> Vec4 { Vec4::new(self * rhs.x, self * rhs.y, self * rhs.z, self * rhs.w) }
 }
 
-impl std::fmt::Debug for Vec4 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for Vec4 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self)
     }
 }
@@ -1,23 +1,60 @@
 //--------------------------------------------------------------------
 // lib.rs
 //--------------------------------------------------------------------
-// Declaration of all modules occurs here
+// Declaration of all modules occurs here; without the std feature, only the
+// geometry and the curves are available (with the no_std feature providing
+// their float math)
 //--------------------------------------------------------------------
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(any(feature = "std", feature = "no_std")))]
+compile_error!("path-processor needs either the std feature or the no_std one, for the float math");
+
+#[cfg(feature = "std")]
 extern crate approx;
 extern crate derive_more;
+#[cfg(feature = "std")]
 extern crate ordered_float;
+#[cfg(feature = "std")]
 extern crate roots;
 extern crate arrayvec;
+#[cfg(feature = "no_std")]
+extern crate libm;
 
+// The helpers the paths use are unused on their own
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 mod geometry;
+#[cfg(feature = "std")]
 mod path;
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 mod curve;
+#[cfg(feature = "std")]
 mod union_find;
+#[cfg(feature = "std")]
 mod pause;
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 mod vec_utils;
+#[cfg(not(feature = "std"))]
+mod roots;
+#[cfg(feature = "std")]
 mod merge;
 
-pub use geometry::{Coord, Vec2};
+#[cfg(all(feature = "std", any(test, feature = "bench")))]
+pub mod test_paths;
+
+pub use geometry::{Coord, Vec2, Rect, Vec4, Geometry, canonical};
+pub use geometry::{convex_hull, simplify_polygon, polygon_winding, polygons_overlap, dedup_points,
+    segments_intersect};
+#[cfg(feature = "std")]
 pub use path::*;
 pub use curve::*;
+
+// The generic helpers for vectors used throughout the crate
+#[cfg(feature = "std")]
+pub mod utils {
+    pub use crate::vec_utils::{partition_inplace_false_first, RemoveIndices, ExtractAll, arrayvec_dedup,
+        arrayvec_dedup_by};
+}
@@ -0,0 +1,117 @@
+//------------------------------------------------------------------------------
+// adjacency.rs
+//------------------------------------------------------------------------------
+// Provides the adjacency information of the plain triangles of a compiled
+// drawing, for post-processing of the generated meshes
+//------------------------------------------------------------------------------
+
+use crate::geometry::*;
+use super::subdivision_structs::Triangle;
+use std::collections::HashMap;
+
+// The triangles as an indexed mesh, along with the triangles that share each of their edges; the
+// edge i of a triangle goes from its vertex i to its vertex (i+1) % 3
+#[derive(Clone, Debug)]
+pub struct TriangleAdjacency {
+    pub vertices: Vec<Vec2>,
+    pub indices: Vec<[u32; 3]>,
+    // The triangles that contain each edge, keyed by its vertex indices (the smaller one first)
+    pub edges: HashMap<(u32, u32), Vec<usize>>
+}
+
+impl TriangleAdjacency {
+    // The vertices are deduplicated by snapping them to the EPSILON grid, since the triangles of
+    // different faces may compute their shared vertices with slightly different rounding; each one
+    // keeps the position it was first seen at
+    pub fn new(triangles: &[Triangle]) -> TriangleAdjacency {
+        let mut vertices = Vec::new();
+        let mut vertex_indices = HashMap::new();
+        let mut indices = Vec::with_capacity(triangles.len());
+        let mut edges = HashMap::<_, Vec<_>>::new();
+
+        for (k, t) in triangles.iter().enumerate() {
+            let mut tri = [0; 3];
+            for (i, v) in [t.a, t.b, t.c].iter().enumerate() {
+                let q = v.snap(EPSILON) / EPSILON;
+                tri[i] = *vertex_indices.entry((q.x as i64, q.y as i64)).or_insert_with(|| {
+                    vertices.push(*v);
+                    (vertices.len() - 1) as u32
+                });
+            }
+
+            for i in 0..3 { edges.entry(edge_key(tri[i], tri[(i+1) % 3])).or_default().push(k); }
+            indices.push(tri);
+        }
+
+        TriangleAdjacency { vertices, indices, edges }
+    }
+
+    // The triangle across the edge i of the given triangle, if exactly one other triangle shares it
+    pub fn neighbor(&self, triangle: usize, edge: usize) -> Option<usize> {
+        let tri = self.indices[triangle];
+        match self.edges[&edge_key(tri[edge], tri[(edge+1) % 3])].as_slice() {
+            &[t1, t2] => Some(if t1 == triangle { t2 } else { t1 }),
+            _ => None
+        }
+    }
+
+    // The edges that belong to a single triangle, which form the outline of the mesh; they are
+    // oriented like their triangles
+    pub fn boundary_edges(&self) -> Vec<(u32, u32)> {
+        let mut boundary = Vec::new();
+        for tri in &self.indices {
+            for i in 0..3 {
+                let (v1, v2) = (tri[i], tri[(i+1) % 3]);
+                if self.edges[&edge_key(v1, v2)].len() == 1 { boundary.push((v1, v2)); }
+            }
+        }
+        boundary
+    }
+}
+
+fn edge_key(v1: u32, v2: u32) -> (u32, u32) { (v1.min(v2), v1.max(v2)) }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_adjacency() {
+        // A square split along its diagonal, plus a triangle hanging from its right side
+        let pts = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0), Vec2::new(2.0, 0.5)];
+        let triangles = [Triangle::new(pts[0], pts[1], pts[2]), Triangle::new(pts[0], pts[2], pts[3]),
+            Triangle::new(pts[1], pts[4], pts[2])];
+
+        let adjacency = TriangleAdjacency::new(&triangles);
+        assert_eq!(adjacency.vertices.len(), 5);
+        assert_eq!(adjacency.edges.len(), 7);
+
+        // The first triangle touches both others
+        let neighbors: Vec<_> = (0..3).filter_map(|e| adjacency.neighbor(0, e)).collect();
+        assert_eq!(neighbors.len(), 2);
+        assert!(neighbors.contains(&1) && neighbors.contains(&2));
+        assert_eq!((0..3).filter_map(|e| adjacency.neighbor(1, e)).collect::<Vec<_>>(), vec![0]);
+
+        // The outline is a pentagon, going counterclockwise
+        let boundary = adjacency.boundary_edges();
+        assert_eq!(boundary.len(), 5);
+        let area: Coord = boundary.iter()
+            .map(|&(v1, v2)| adjacency.vertices[v1 as usize].cross(adjacency.vertices[v2 as usize])).sum();
+        assert!(area.roughly_equals(2.0 * 1.5));
+    }
+
+    #[test]
+    fn test_perturbed_vertices() {
+        // The same square split along its diagonal, with the shared vertices a few ULPs apart
+        let ulps = |x: Coord, n: i64| Coord::from_bits((x.to_bits() as i64 + n) as u64);
+        let (a, c) = (Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0));
+        let (a2, c2) = (Vec2::new(ulps(0.0, 3), 0.0), Vec2::new(ulps(1.0, -2), ulps(1.0, 1)));
+        let triangles = [Triangle::new(a, Vec2::new(1.0, 0.0), c), Triangle::new(a2, c2, Vec2::new(0.0, 1.0))];
+
+        let adjacency = TriangleAdjacency::new(&triangles);
+        assert_eq!(adjacency.vertices.len(), 4);
+        assert_eq!(adjacency.neighbor(0, 2), Some(1));
+        assert_eq!(adjacency.boundary_edges().len(), 4);
+        assert!(adjacency.vertices.contains(&c) && !adjacency.vertices.contains(&c2));
+    }
+}
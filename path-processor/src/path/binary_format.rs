@@ -0,0 +1,163 @@
+//------------------------------------------------------------------------------
+// binary_format.rs
+//------------------------------------------------------------------------------
+// Provides a fixed binary format for the compiled drawings, so they can be
+// baked to disk and loaded without running the path processor again
+//------------------------------------------------------------------------------
+
+use std::io::{self, Read, Write};
+use crate::geometry::*;
+use super::*;
+
+// The layout, all little-endian: the magic, the version, and then the plain, curve and double
+// curve triangles, each array prefixed by its length as an u64; the coordinates are f64s, and the
+// double curve vertices end with a byte for whether they are a disjoint union
+const MAGIC: [u8; 4] = *b"PRLD";
+pub const COMPILED_DRAWING_VERSION: u32 = 1;
+
+// The most elements preallocated for each array, so a corrupt length can't make the reader
+// allocate a huge vector before finding out the input is too short
+const MAX_PREALLOCATED: usize = 1 << 16;
+
+impl CompiledDrawing {
+    pub fn write_binary(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&MAGIC)?;
+        w.write_all(&COMPILED_DRAWING_VERSION.to_le_bytes())?;
+
+        write_len(w, self.triangles.len())?;
+        for t in &self.triangles {
+            for &p in &[t.a, t.b, t.c] { write_vec2(w, p)?; }
+        }
+
+        write_len(w, self.curve_triangles.len())?;
+        for t in &self.curve_triangles {
+            for v in &[t.a, t.b, t.c] {
+                write_vec2(w, v.pos)?;
+                write_vec4(w, v.tex)?;
+            }
+        }
+
+        write_len(w, self.double_curve_triangles.len())?;
+        for t in &self.double_curve_triangles {
+            for v in &[t.a, t.b, t.c] {
+                write_vec2(w, v.pos)?;
+                write_vec4(w, v.tex0)?;
+                write_vec4(w, v.tex1)?;
+                w.write_all(&[v.is_disjoint_union() as u8])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Read a drawing written by write_binary; a wrong magic or an unknown version is reported as
+    // invalid data, and a truncated input as an unexpected end of file
+    pub fn read_binary<R: Read>(r: &mut R) -> io::Result<CompiledDrawing> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if magic != MAGIC { return Err(invalid_data("not a compiled drawing")); }
+
+        let mut version = [0; 4];
+        r.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != COMPILED_DRAWING_VERSION {
+            return Err(invalid_data(&format!("unsupported compiled drawing version {}", version)));
+        }
+
+        let triangles = read_array(r, |r| Ok(Triangle { a: read_vec2(r)?, b: read_vec2(r)?, c: read_vec2(r)? }))?;
+
+        let vertex = |r: &mut R| -> io::Result<_> { Ok(CurveVertex { pos: read_vec2(r)?, tex: read_vec4(r)? }) };
+        let curve_triangles = read_array(r, |r| Ok(CurveTriangle { a: vertex(r)?, b: vertex(r)?, c: vertex(r)? }))?;
+
+        let vertex = |r: &mut R| -> io::Result<_> {
+            let (pos, tex0, tex1) = (read_vec2(r)?, read_vec4(r)?, read_vec4(r)?);
+            let mut disjoint_union = [0];
+            r.read_exact(&mut disjoint_union)?;
+            Ok(DoubleCurveVertex::new(pos, tex0, tex1, disjoint_union[0] != 0))
+        };
+        let double_curve_triangles = read_array(r, |r| Ok(DoubleCurveTriangle { a: vertex(r)?, b: vertex(r)?, c: vertex(r)? }))?;
+
+        Ok(CompiledDrawing { triangles, curve_triangles, double_curve_triangles })
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, message) }
+
+fn write_len(w: &mut impl Write, len: usize) -> io::Result<()> { w.write_all(&(len as u64).to_le_bytes()) }
+
+fn write_vec2(w: &mut impl Write, v: Vec2) -> io::Result<()> {
+    w.write_all(&v.x.to_le_bytes())?;
+    w.write_all(&v.y.to_le_bytes())
+}
+
+fn write_vec4(w: &mut impl Write, v: Vec4) -> io::Result<()> {
+    for c in &[v.x, v.y, v.z, v.w] { w.write_all(&c.to_le_bytes())?; }
+    Ok(())
+}
+
+fn read_coord<R: Read>(r: &mut R) -> io::Result<Coord> {
+    let mut bytes = [0; 8];
+    r.read_exact(&mut bytes)?;
+    Ok(Coord::from_le_bytes(bytes))
+}
+
+fn read_vec2<R: Read>(r: &mut R) -> io::Result<Vec2> { Ok(Vec2::new(read_coord(r)?, read_coord(r)?)) }
+
+fn read_vec4<R: Read>(r: &mut R) -> io::Result<Vec4> {
+    Ok(Vec4 { x: read_coord(r)?, y: read_coord(r)?, z: read_coord(r)?, w: read_coord(r)? })
+}
+
+fn read_array<R: Read, T>(r: &mut R, mut read: impl FnMut(&mut R) -> io::Result<T>) -> io::Result<Vec<T>> {
+    let mut len = [0; 8];
+    r.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+
+    let mut result = Vec::with_capacity((len as usize).min(MAX_PREALLOCATED));
+    for _ in 0..len { result.push(read(r)?); }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_binary_round_trip() {
+        let path = PathBuilder::new()
+            .move_to(Vec2::new(0.0, 0.0)).quad_to(Vec2::new(2.0, -1.0), Vec2::new(4.0, 0.0))
+            .cubic_to(Vec2::new(5.0, 2.0), Vec2::new(3.0, 4.0), Vec2::new(2.0, 4.0))
+            .arc_to(Vec2::new(2.0, 2.0), 0.0, false, true, Vec2::new(0.0, 0.0)).close()
+            .build();
+        let drawing = compile_path(&path, FillRule::NonZero);
+        assert!(!drawing.triangles.is_empty() && !drawing.curve_triangles.is_empty());
+
+        let mut bytes = Vec::new();
+        drawing.write_binary(&mut bytes).unwrap();
+        let read = CompiledDrawing::read_binary(&mut bytes.as_slice()).unwrap();
+
+        // The triangles go through unchanged, bit by bit
+        let mut again = Vec::new();
+        read.write_binary(&mut again).unwrap();
+        assert_eq!(bytes, again);
+        assert_eq!(read.triangles.len(), drawing.triangles.len());
+        assert_eq!(read.double_curve_triangles.len(), drawing.double_curve_triangles.len());
+        for (t, u) in read.curve_triangles.iter().zip(&drawing.curve_triangles) {
+            assert!(t.a.pos == u.a.pos && t.b.tex == u.b.tex && t.c.pos == u.c.pos);
+        }
+    }
+
+    #[test]
+    fn test_binary_errors() {
+        let mut bytes = Vec::new();
+        CompiledDrawing::empty().write_binary(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 4 + 4 + 3 * 8);
+        assert!(CompiledDrawing::read_binary(&mut bytes.as_slice()).is_ok());
+
+        let kind = |bytes: &[u8]| CompiledDrawing::read_binary(&mut &bytes[..]).unwrap_err().kind();
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 2;
+        assert_eq!(kind(&wrong_version), io::ErrorKind::InvalidData);
+        assert_eq!(kind(b"SVG!\x01\0\0\0"), io::ErrorKind::InvalidData);
+        assert_eq!(kind(&bytes[..bytes.len() - 1]), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,165 @@
+//------------------------------------------------------------------------------
+// builder.rs
+//------------------------------------------------------------------------------
+// Provides a builder to assemble paths programmatically, with the same set of
+// commands as the SVG paths
+//------------------------------------------------------------------------------
+
+use crate::geometry::*;
+use super::*;
+
+// The relative variants are taken from the current point, which is where the last command ended
+// (or the start of the subpath, after close)
+#[derive(Clone, Debug)]
+pub struct PathBuilder {
+    path: Path,
+    current: Vec2,
+    start: Vec2,
+    // Whether the current subpath has drawn anything since its start or its last close
+    drawing: bool,
+    subpath: usize,
+    open_subpaths: Vec<usize>
+}
+
+impl Default for PathBuilder {
+    fn default() -> PathBuilder {
+        PathBuilder { path: Vec::new(), current: Vec2::zero(), start: Vec2::zero(), drawing: false,
+            subpath: 0, open_subpaths: Vec::new() }
+    }
+}
+
+impl PathBuilder {
+    pub fn new() -> PathBuilder { PathBuilder::default() }
+
+    pub fn move_to(mut self, target: Vec2) -> PathBuilder {
+        self.end_subpath();
+        self.path.push(PathCommand::MoveTo(target));
+        self.current = target;
+        self.start = target;
+        self
+    }
+
+    pub fn line_to(self, target: Vec2) -> PathBuilder {
+        self.draw(PathCommand::LineTo(target), target)
+    }
+
+    pub fn quad_to(self, control: Vec2, target: Vec2) -> PathBuilder {
+        self.draw(PathCommand::QuadraticBezierTo(control, target), target)
+    }
+
+    pub fn cubic_to(self, control1: Vec2, control2: Vec2, target: Vec2) -> PathBuilder {
+        self.draw(PathCommand::CubicBezierTo(control1, control2, target), target)
+    }
+
+    // The angle is the rotation of the ellipse's x axis, in degrees, like in the SVG paths
+    pub fn arc_to(self, radii: Vec2, angle: Coord, large_arc: bool, sweep: bool, target: Vec2) -> PathBuilder {
+        self.draw(PathCommand::EllipticArcTo(radii, angle, large_arc, sweep, target), target)
+    }
+
+    pub fn close(mut self) -> PathBuilder {
+        self.path.push(PathCommand::ClosePath);
+        self.current = self.start;
+        self.drawing = false;
+        self
+    }
+
+    pub fn rel_move_to(self, delta: Vec2) -> PathBuilder {
+        let target = self.current + delta;
+        self.move_to(target)
+    }
+
+    pub fn rel_line_to(self, delta: Vec2) -> PathBuilder {
+        let c = self.current;
+        self.line_to(c + delta)
+    }
+
+    pub fn rel_quad_to(self, control: Vec2, delta: Vec2) -> PathBuilder {
+        let c = self.current;
+        self.quad_to(c + control, c + delta)
+    }
+
+    pub fn rel_cubic_to(self, control1: Vec2, control2: Vec2, delta: Vec2) -> PathBuilder {
+        let c = self.current;
+        self.cubic_to(c + control1, c + control2, c + delta)
+    }
+
+    pub fn rel_arc_to(self, radii: Vec2, angle: Coord, large_arc: bool, sweep: bool, delta: Vec2) -> PathBuilder {
+        let c = self.current;
+        self.arc_to(radii, angle, large_arc, sweep, c + delta)
+    }
+
+    // The point the next command starts from
+    pub fn current_point(&self) -> Vec2 { self.current }
+
+    // The indices of the subpaths (counting every move_to) that drew something and weren't closed;
+    // they are closed with a line when filled, or handled according to an OpenSubpathPolicy
+    pub fn open_subpaths(&self) -> Vec<usize> {
+        let mut open = self.open_subpaths.clone();
+        if self.drawing { open.push(self.subpath); }
+        open
+    }
+
+    pub fn build(self) -> Path { self.path }
+
+    // Build the path, rejecting it if any of its coordinates is not finite
+    pub fn try_build(self) -> std::result::Result<Path, ValidationError> {
+        validate_path(&self.path)?;
+        Ok(self.path)
+    }
+
+    fn draw(mut self, command: PathCommand, target: Vec2) -> PathBuilder {
+        self.path.push(command);
+        self.current = target;
+        self.drawing = true;
+        self
+    }
+
+    fn end_subpath(&mut self) {
+        if self.drawing { self.open_subpaths.push(self.subpath); }
+        if !self.path.is_empty() { self.subpath += 1; }
+        self.drawing = false;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_path_builder() {
+        let path = PathBuilder::new()
+            .move_to(Vec2::new(1.0, 1.0))
+            .rel_line_to(Vec2::new(2.0, 0.0))
+            .rel_quad_to(Vec2::new(1.0, 1.0), Vec2::new(0.0, 2.0))
+            .line_to(Vec2::new(1.0, 3.0))
+            .close()
+            .rel_move_to(Vec2::new(5.0, 0.0))
+            .rel_arc_to(Vec2::new(1.0, 1.0), 0.0, false, true, Vec2::new(2.0, 0.0))
+            .build();
+
+        assert_eq!(path.len(), 7);
+        match (path[2], path[5], path[6]) {
+            (PathCommand::QuadraticBezierTo(c, t), PathCommand::MoveTo(m), PathCommand::EllipticArcTo(_, _, _, _, a)) => {
+                assert_eq!((c, t), (Vec2::new(4.0, 2.0), Vec2::new(3.0, 3.0)));
+                // The close goes back to the start of the subpath
+                assert_eq!(m, Vec2::new(6.0, 1.0));
+                assert_eq!(a, Vec2::new(8.0, 1.0));
+            }
+            _ => panic!("Unexpected commands {:?}", path)
+        }
+    }
+
+    #[test]
+    fn test_path_builder_validation() {
+        let builder = PathBuilder::new()
+            .move_to(Vec2::new(0.0, 0.0)).line_to(Vec2::new(1.0, 0.0)).line_to(Vec2::new(0.0, 1.0)).close()
+            .move_to(Vec2::new(2.0, 0.0)).line_to(Vec2::new(3.0, 0.0))
+            .move_to(Vec2::new(4.0, 0.0))
+            .move_to(Vec2::new(5.0, 0.0)).cubic_to(Vec2::new(5.0, 1.0), Vec2::new(6.0, 1.0), Vec2::new(6.0, 0.0));
+        assert_eq!(builder.open_subpaths(), vec![1, 3]);
+        assert!(builder.clone().try_build().is_ok());
+
+        let error = builder.line_to(Vec2::new(Coord::NAN, 0.0)).try_build().unwrap_err();
+        assert_eq!(error.index, 9);
+    }
+}
@@ -0,0 +1,120 @@
+//------------------------------------------------------------------------------
+// clipping.rs
+//------------------------------------------------------------------------------
+// Provides the clipping of closed contours to rectangles, so only the visible
+// part of a huge path needs to be processed
+//------------------------------------------------------------------------------
+
+use crate::geometry::*;
+use crate::curve::*;
+
+// Clip the closed contours to the rectangle, producing closed contours that fill the same region
+// inside it (under either fill rule) and nothing outside; this is Sutherland-Hodgman extended to
+// curves, clipping against each side's half-plane in turn. The edges along the rectangle may
+// overlap each other, so the result should go through split_comps before being triangulated
+pub fn clip_to_rect(curves: &[Curve], r: Rect) -> Vec<Curve> {
+    let (min, max) = r.corners();
+
+    // Contours that don't cross the boundary and start inside are inside as a whole
+    let inside = |p: Vec2| r.clamp_point(p).roughly_equals(p);
+    if curves.iter().all(|c| c.intersect_rect(r).is_empty() && inside(c.at(0.0))) { return curves.to_vec(); }
+
+    let curves = clip_to_half_plane(curves.to_vec(), true, min.x, 1.0);
+    let curves = clip_to_half_plane(curves, true, max.x, -1.0);
+    let curves = clip_to_half_plane(curves, false, min.y, 1.0);
+    clip_to_half_plane(curves, false, max.y, -1.0)
+}
+
+// Clip the curves to the half-plane where sign * (x - value) >= 0 (or y, if not vertical); each
+// run of curve pieces outside is replaced by the segment between the projections of its ends on
+// the boundary line, which changes the winding numbers only on the outside
+fn clip_to_half_plane(curves: Vec<Curve>, vertical: bool, value: Coord, sign: Coord) -> Vec<Curve> {
+    let coord = |p: Vec2| if vertical { p.x } else { p.y };
+    let project = |p: Vec2| if vertical { Vec2::new(value, p.y) } else { Vec2::new(p.x, value) };
+    let inside = |p: Vec2| sign * (coord(p) - value) >= -EPSILON;
+
+    let mut result = Vec::with_capacity(curves.len());
+    let mut excursion: Option<(Vec2, Vec2)> = None;
+    let flush = |result: &mut Vec<Curve>, excursion: &mut Option<(Vec2, Vec2)>| {
+        if let Some((start, end)) = excursion.take() {
+            let line = Curve::line(project(start), project(end));
+            if !is_curve_degenerate(&line) { result.push(line); }
+        }
+    };
+
+    for curve in curves {
+        let roots = if vertical { curve.intersection_x(value) } else { curve.intersection_y(value) };
+        let mut ts: Vec<_> = roots.as_ref().iter().copied().filter(|&t| t > 0.0 && t < 1.0).collect();
+        ts.push(0.0);
+        ts.push(1.0);
+        ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        for w in ts.windows(2) {
+            let piece = curve.subcurve(w[0], w[1]);
+            if is_curve_degenerate(&piece) { continue; }
+            let (a, b) = (piece.at(0.0), piece.at(1.0));
+
+            // An excursion ends where the contour does, too
+            if excursion.is_some_and(|(_, end)| !end.roughly_equals(a)) { flush(&mut result, &mut excursion); }
+
+            // The pieces don't cross the line, so their midpoints tell on which side they are
+            if inside(piece.at(0.5)) {
+                flush(&mut result, &mut excursion);
+                result.push(piece);
+            } else {
+                excursion = Some((excursion.map_or(a, |(start, _)| start), b));
+            }
+        }
+    }
+
+    flush(&mut result, &mut excursion);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::path::{split_comps, FillRule};
+    use crate::test_paths::rectangle;
+
+    #[test]
+    fn test_clip_to_rect() {
+        let r = Rect::new(0.0, 0.0, 2.0, 2.0);
+        let center = Vec2::new(2.0, 1.0);
+        let mut curves = vec![Curve::circle(center, 1.5, Vec2::new(1.0, 0.0), Vec2::new(-1.0, 0.0), false),
+            Curve::circle(center, 1.5, Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0), false)];
+        // A contour that is outside as a whole, wrapping around a corner
+        curves.extend(rectangle(-3.0, -3.0, 2.0, 6.0));
+
+        let clipped = clip_to_rect(&curves, r);
+        for c in &clipped {
+            let (a, b) = r.corners();
+            let bigger = Rect::from_corners(a - Vec2::new(EPSILON, EPSILON), b + Vec2::new(EPSILON, EPSILON));
+            assert!(bigger.contains_point(c.at(0.0)) && bigger.contains_point(c.at(0.5)), "{}", c);
+        }
+
+        // The clipped faces fill the part of the circle inside the rectangle
+        let faces = split_comps(clipped, FillRule::NonZero);
+        for i in 0..40 {
+            for j in 0..40 {
+                let p = Vec2::new(-0.9 + i as Coord * 0.1, -0.9 + j as Coord * 0.1) + Vec2::new(0.013, 0.017);
+                let expected = r.contains_point(p) && (p - center).length() < 1.5;
+                assert_eq!(faces.iter().any(|f| f.contains_point(p)), expected, "{}", p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_clip_to_rect_around() {
+        let r = Rect::new(0.0, 0.0, 2.0, 2.0);
+
+        // A contour around the rectangle becomes the rectangle itself, and one inside it is kept
+        let mut curves = rectangle(-1.0, -1.0, 4.0, 4.0);
+        let faces = split_comps(clip_to_rect(&curves, r), FillRule::NonZero);
+        assert_eq!(faces.len(), 1);
+        assert!(faces[0].min_vertex().unwrap().roughly_equals(Vec2::new(0.0, 0.0)));
+
+        curves = rectangle(0.5, 0.5, 1.0, 1.0);
+        assert_eq!(clip_to_rect(&curves, r).len(), 4);
+    }
+}
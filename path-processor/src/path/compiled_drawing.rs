@@ -10,6 +10,7 @@ use subdivision::*;
 use subdivision_structs::*;
 use curve_vertices::*;
 use triangulation::*;
+use crate::curve::simplify_curves;
 
 #[derive(Debug)]
 pub struct CompiledDrawing {
@@ -20,19 +21,74 @@ pub struct CompiledDrawing {
 
 impl CompiledDrawing {
     pub fn concat_many(drawings: impl Iterator<Item = CompiledDrawing>) -> CompiledDrawing {
+        let mut result = CompiledDrawing::empty();
+        for drawing in drawings { result.append(drawing); }
+        result
+    }
+
+    // Move the triangles of another drawing to the end of this one
+    pub fn append(&mut self, mut other: CompiledDrawing) {
+        self.triangles.append(&mut other.triangles);
+        self.curve_triangles.append(&mut other.curve_triangles);
+        self.double_curve_triangles.append(&mut other.double_curve_triangles);
+    }
+
+    // Reserve space for at least the specified amount of additional triangles
+    pub fn reserve(&mut self, triangles: usize, curve_triangles: usize, double_curve_triangles: usize) {
+        self.triangles.reserve(triangles);
+        self.curve_triangles.reserve(curve_triangles);
+        self.double_curve_triangles.reserve(double_curve_triangles);
+    }
+
+    // Rewind all the triangles to the given orientation; screen spaces with the y axis pointing
+    // down need clockwise triangles (in math space) to face the viewer
+    pub fn orient(&mut self, ccw: bool) {
+        for t in &mut self.triangles { *t = Triangle::new_oriented(t.a, t.b, t.c, ccw); }
+        for t in &mut self.curve_triangles { *t = CurveTriangle::new_oriented(t.a, t.b, t.c, ccw); }
+        for t in &mut self.double_curve_triangles { *t = DoubleCurveTriangle::new_oriented(t.a, t.b, t.c, ccw); }
+    }
+
+    // Flatten the curve triangles into plain triangles that stay within tolerance of the true
+    // boundary, for the renderers that can't run the Loop-Blinn shaders
+    pub fn tessellate_curves(&self, tolerance: Coord) -> Vec<Triangle> {
         let mut triangles = Vec::new();
-        let mut curve_triangles = Vec::new();
-        let mut double_curve_triangles = Vec::new();
+        for tri in &self.curve_triangles { tri.tessellate(tolerance, &mut triangles); }
+        for tri in &self.double_curve_triangles { tri.tessellate(tolerance, &mut triangles); }
+        triangles
+    }
 
-        for mut drawing in drawings {
-            triangles.append(&mut drawing.triangles);
-            curve_triangles.append(&mut drawing.curve_triangles);
-            double_curve_triangles.append(&mut drawing.double_curve_triangles);
+    // Snap every vertex position to the nearest multiple of grid, keeping the texture coordinates,
+    // for deterministic output; coarse grids can collapse triangles, so the degenerate ones are dropped
+    pub fn quantize(&mut self, grid: Coord) {
+        for t in &mut self.triangles {
+            *t = Triangle { a: t.a.snap(grid), b: t.b.snap(grid), c: t.c.snap(grid) };
+        }
+        for t in &mut self.curve_triangles {
+            for v in [&mut t.a, &mut t.b, &mut t.c].iter_mut() { v.pos = v.pos.snap(grid); }
+        }
+        for t in &mut self.double_curve_triangles {
+            for v in [&mut t.a, &mut t.b, &mut t.c].iter_mut() { v.pos = v.pos.snap(grid); }
         }
 
-        CompiledDrawing { triangles, curve_triangles, double_curve_triangles }
+        self.triangles.retain(|t| !t.is_degenerate());
+        self.curve_triangles.retain(|t| !t.is_degenerate());
+        self.double_curve_triangles.retain(|t| !t.is_degenerate());
     }
 
+    // The convex hull of all the triangles' vertices, in counterclockwise order; the curve
+    // triangles enclose their curves, so the hull covers the whole drawing
+    pub fn convex_hull(&self) -> Vec<Vec2> {
+        let mut points = Vec::with_capacity(3 * (self.triangles.len() + self.curve_triangles.len()
+            + self.double_curve_triangles.len()));
+        for t in &self.triangles { points.extend_from_slice(&[t.a, t.b, t.c]); }
+        for t in &self.curve_triangles { points.extend_from_slice(&[t.a.pos, t.b.pos, t.c.pos]); }
+        for t in &self.double_curve_triangles { points.extend_from_slice(&[t.a.pos, t.b.pos, t.c.pos]); }
+        convex_hull(points)
+    }
+
+    // The adjacency of the plain triangles, for mesh post-processing
+    pub fn build_adjacency(&self) -> TriangleAdjacency { TriangleAdjacency::new(&self.triangles) }
+
     pub fn empty() -> CompiledDrawing {
         CompiledDrawing {
             triangles: Vec::new(),
@@ -42,51 +98,114 @@ impl CompiledDrawing {
     }
 
     pub fn from_face(face: FillFace) -> CompiledDrawing {
+        CompiledDrawing::from_face_with(face, &FusionParams::default(), usize::MAX).0
+    }
+
+    pub fn from_face_with_params(face: FillFace, params: &FusionParams) -> CompiledDrawing {
+        CompiledDrawing::from_face_with(face, params, usize::MAX).0
+    }
+
+    pub fn from_face_with_limit(face: FillFace, max_subdivisions: usize) -> (CompiledDrawing, usize) {
+        CompiledDrawing::from_face_with(face, &FusionParams::default(), max_subdivisions)
+    }
+
+    // Compile the face doing at most max_subdivisions curve subdivisions, also returning
+    // how many were done (if it equals the limit, the curves may still overlap)
+    pub fn from_face_with(face: FillFace, params: &FusionParams, max_subdivisions: usize) -> (CompiledDrawing, usize) {
         // Simplify the face by subdividing overlapping curves
         //let then = std::time::Instant::now();
-        let face = subdivide_overlapping(face);
+        let (face, subdivisions) = subdivide_overlapping(face, params, max_subdivisions);
         //let now = std::time::Instant::now();
         //println!("Time spent in the subdivision: {:?}", now.duration_since(then));
 
+        (CompiledDrawing::from_subdivided_face(face, Some(params)), subdivisions)
+    }
+
+    // Compile the face without subdividing the overlapping curves or fusing them into double curve
+    // triangles; much faster, but only correct if the enclosing polygons of the curves don't
+    // overlap, as on clean font outlines
+    pub fn from_face_no_fusion(face: FillFace) -> CompiledDrawing {
+        CompiledDrawing::from_subdivided_face(face, None)
+    }
+
+    // Compile the face whose curves were already subdivided, fusing them only if there are params
+    fn from_subdivided_face(face: FillFace, params: Option<&FusionParams>) -> CompiledDrawing {
         // Build the fill polygons and triangulate them
         let mut curve_triangles = Vec::new();
         let mut double_curve_triangles = Vec::new();
 
         let polygons = face.contours.into_iter().map(|c| {
-            build_polygon_and_curves(c.as_ref(), &mut curve_triangles, &mut double_curve_triangles)
+            build_polygon_and_curves(c.as_ref(), params, &mut curve_triangles, &mut double_curve_triangles)
         });
 
         //let then = std::time::Instant::now();
-        let triangles = triangulate(polygons);
+        let triangles = triangulate_oriented(polygons);
         //let now = std::time::Instant::now();
         //println!("Time spent in the triangulation: {:?}", now.duration_since(then));
 
+        // Collinear enclosing polygons generate zero-area curve triangles, so remove them too
+        curve_triangles.retain(|t| !t.is_degenerate());
+        double_curve_triangles.retain(|t| !t.is_degenerate());
+
         CompiledDrawing { triangles, curve_triangles, double_curve_triangles }
     }
 }
 
-fn build_polygon_and_curves(contour: &[Curve], curve_triangles: &mut Vec<CurveTriangle>,
+// How compile_path_with runs the pipeline; the default options are the ones of compile_path
+#[derive(Clone, Copy, Debug)]
+pub struct CompileOptions {
+    // What to do with the subpaths that don't end where they start; the fill assumes closed
+    // contours, so leaving them open only makes sense if the caller knows what it is doing
+    pub auto_close: AutoClose,
+    // Whether the curves that meet at sharp angles are fused into double curve triangles
+    pub fusion: FusionMode,
+    // How the cubics are split before building their curve triangles
+    pub curve_subdivision: CurveSubdiv
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        CompileOptions { auto_close: AutoClose::Yes, fusion: FusionMode::Auto, curve_subdivision: CurveSubdiv::None }
+    }
+}
+
+// Run the whole pipeline on a path, generating its compiled drawing
+pub fn compile_path(path: &Path, fill_rule: FillRule) -> CompiledDrawing {
+    compile_path_with(path, fill_rule, &CompileOptions::default()).unwrap()
+}
+
+// Same as compile_path, with the given options; it fails only if the open subpaths are an error
+pub fn compile_path_with(path: &Path, fill_rule: FillRule, options: &CompileOptions)
+    -> std::result::Result<CompiledDrawing, OpenSubpathError> {
+    let curves = simplify_curves(path_to_curves_auto_close(path, options.auto_close)?);
+    let faces = split_comps_iter(curves, fill_rule).map(|f| subdivide_by_arc_length(f, options.curve_subdivision));
+    Ok(match options.fusion {
+        FusionMode::Auto => CompiledDrawing::concat_many(faces.map(CompiledDrawing::from_face)),
+        FusionMode::Off => CompiledDrawing::concat_many(faces.map(CompiledDrawing::from_face_no_fusion))
+    })
+}
+
+fn build_polygon_and_curves(contour: &[Curve], params: Option<&FusionParams>, curve_triangles: &mut Vec<CurveTriangle>,
     double_curve_triangles: &mut Vec<DoubleCurveTriangle>) -> Vec<Vec2> {
     if contour.is_empty() { return Vec::new(); }
+    let fusable = |c1: &Curve, c2: &Curve| params.is_some_and(|params| are_curves_fusable(c1, c2, params));
     // "Guess" a capacity for the list
     let mut list = Vec::with_capacity((1.4 * contour.len() as f64) as usize);
 
     // Check first if the last and first curve aren't joinable
-    let last_first_join = are_curves_fusable(&contour[contour.len()-1], &contour[0]);
+    let last_first_join = fusable(&contour[contour.len()-1], &contour[0]);
     if last_first_join
     { 
         list.push(contour[0].at(1.0));
         double_curve_triangles.extend(DoubleCurveVertex::make_triangle_fan(
             &fuse_curve_vertices(&contour[contour.len()-1], &contour[0])));
-    } else if !contour[0].is_line() {
-        curve_triangles.extend(CurveVertex::make_triangle_fan(&curve_vertices(&contour[0])));
     }
 
     // Now, scan the curve list for pairs of scannable curves
     let k = if last_first_join { 1 } else { 0 };
     let mut i = k;
     while i < contour.len()-k {
-        if i < contour.len()-1 && are_curves_fusable(&contour[i], &contour[i+1]) {
+        if i < contour.len()-1 && fusable(&contour[i], &contour[i+1]) {
             // Add the curve triangles
             double_curve_triangles.extend(DoubleCurveVertex::make_triangle_fan(
                 &fuse_curve_vertices(&contour[i], &contour[i+1])));
@@ -125,3 +244,267 @@ fn build_polygon_and_curves(contour: &[Curve], curve_triangles: &mut Vec<CurveTr
     list
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_compile_square() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 4.0)),
+            PathCommand::LineTo(Vec2::new(0.0, 4.0))
+        ];
+
+        let drawing = compile_path(&path, FillRule::NonZero);
+        let area: Coord = drawing.triangles.iter().map(|t| (t.b - t.a).cross(t.c - t.a) / 2.0).sum();
+        assert!(area.roughly_equals(16.0));
+        assert!(drawing.curve_triangles.is_empty());
+        assert!(drawing.double_curve_triangles.is_empty());
+    }
+
+    #[test]
+    fn test_append_and_reserve() {
+        let square = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 4.0)),
+            PathCommand::LineTo(Vec2::new(0.0, 4.0))
+        ];
+        let curved = vec![
+            PathCommand::MoveTo(Vec2::new(6.0, 0.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(8.0, -2.0), Vec2::new(10.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(8.0, 3.0))
+        ];
+
+        // The positions of every vertex, in order, so the contents can be compared
+        let positions = |d: &CompiledDrawing| -> Vec<Vec2> {
+            let mut positions = Vec::new();
+            for t in &d.triangles { positions.extend(&[t.a, t.b, t.c]); }
+            for t in &d.curve_triangles { positions.extend(&[t.a.pos, t.b.pos, t.c.pos]); }
+            for t in &d.double_curve_triangles { positions.extend(&[t.a.pos, t.b.pos, t.c.pos]); }
+            positions
+        };
+
+        let (first, second) = (compile_path(&square, FillRule::NonZero), compile_path(&curved, FillRule::NonZero));
+        assert!(!second.curve_triangles.is_empty());
+        let lengths = |d: &CompiledDrawing| (d.triangles.len(), d.curve_triangles.len(), d.double_curve_triangles.len());
+        let (l1, l2) = (lengths(&first), lengths(&second));
+
+        // Reserving changes nothing but the capacity
+        let mut drawing = compile_path(&square, FillRule::NonZero);
+        drawing.reserve(l2.0, l2.1, l2.2);
+        assert_eq!(positions(&drawing), positions(&first));
+        assert!(drawing.curve_triangles.capacity() >= l1.1 + l2.1);
+
+        // Appending concatenates every array
+        drawing.append(compile_path(&curved, FillRule::NonZero));
+        assert_eq!(lengths(&drawing), (l1.0 + l2.0, l1.1 + l2.1, l1.2 + l2.2));
+        let mut expected = CompiledDrawing::empty();
+        expected.triangles = first.triangles.iter().chain(&second.triangles).copied().collect();
+        expected.curve_triangles = first.curve_triangles.iter().chain(&second.curve_triangles).copied().collect();
+        expected.double_curve_triangles = first.double_curve_triangles.iter().chain(&second.double_curve_triangles).copied().collect();
+        assert_eq!(positions(&drawing), positions(&expected));
+        assert_eq!(positions(&CompiledDrawing::concat_many(vec![first, second].into_iter())), positions(&expected));
+    }
+
+    #[test]
+    fn test_tessellate_curves() {
+        let v = Vec2::new;
+        let paths = vec![
+            // A circle made of two arcs
+            vec![PathCommand::MoveTo(v(0.0, 0.0)),
+                PathCommand::EllipticArcTo(v(2.0, 2.0), 0.0, false, true, v(4.0, 0.0)),
+                PathCommand::EllipticArcTo(v(2.0, 2.0), 0.0, false, true, v(0.0, 0.0))],
+            // A square with a convex and a concave quadratic side
+            vec![PathCommand::MoveTo(v(0.0, 0.0)),
+                PathCommand::QuadraticBezierTo(v(2.0, -2.0), v(4.0, 0.5)),
+                PathCommand::LineTo(v(4.5, 4.0)),
+                PathCommand::QuadraticBezierTo(v(2.0, 2.5), v(-0.5, 4.2))],
+            // The same with cubics
+            vec![PathCommand::MoveTo(v(0.0, 0.0)),
+                PathCommand::CubicBezierTo(v(1.0, -2.0), v(3.0, -1.0), v(4.0, 0.5)),
+                PathCommand::LineTo(v(4.5, 4.0)),
+                PathCommand::CubicBezierTo(v(3.0, 2.0), v(1.0, 3.0), v(-0.5, 4.2))],
+            // A thin crescent, whose spikes fuse the curves into double curve triangles
+            vec![PathCommand::MoveTo(v(0.0, 0.0)),
+                PathCommand::QuadraticBezierTo(v(2.0, 2.0), v(4.0, 0.0)),
+                PathCommand::QuadraticBezierTo(v(2.0, 1.8), v(0.0, 0.0))]
+        ];
+
+        let area = |tris: &[Triangle]| tris.iter().map(|t| (t.b - t.a).cross(t.c - t.a) / 2.0).sum::<Coord>();
+        let tolerance = 1.0 / 256.0;
+
+        for path in &paths {
+            let expected = path_to_closed_curves(path).iter().map(|c| c.winding() / 2.0).sum::<Coord>().abs();
+            let drawing = compile_path(path, FillRule::NonZero);
+            let curve_tris = drawing.tessellate_curves(tolerance);
+            assert!(!curve_tris.is_empty());
+
+            // The error is bounded by the boundary's length times the tolerance
+            let total = area(&drawing.triangles) + area(&curve_tris);
+            assert!((total - expected).abs() < 32.0 * tolerance, "{} != {}", total, expected);
+            assert!(drawing.double_curve_triangles.iter().all(|t| t.a.tex0.x.is_finite() && t.a.tex1.x.is_finite()));
+        }
+    }
+
+    #[test]
+    fn test_orient() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(2.0, -2.0), Vec2::new(4.0, 0.5)),
+            PathCommand::LineTo(Vec2::new(4.5, 4.0)),
+            PathCommand::LineTo(Vec2::new(-0.5, 4.2))
+        ];
+
+        let mut drawing = compile_path(&path, FillRule::NonZero);
+        let tex_before: Vec<_> = drawing.curve_triangles.iter().map(|t| (t.a.tex, t.a.pos)).collect();
+        drawing.orient(false);
+
+        let signed_area = |a: Vec2, b: Vec2, c: Vec2| (b - a).cross(c - a);
+        assert!(drawing.triangles.iter().all(|t| signed_area(t.a, t.b, t.c) < 0.0));
+        assert!(drawing.curve_triangles.iter().all(|t| signed_area(t.a.pos, t.b.pos, t.c.pos) < 0.0));
+
+        // The texture coordinates must stay attached to their positions
+        for (t, (tex, pos)) in drawing.curve_triangles.iter().zip(tex_before) {
+            assert!(t.a.pos == pos && t.a.tex == tex);
+        }
+
+        drawing.orient(true);
+        assert!(drawing.triangles.iter().all(|t| signed_area(t.a, t.b, t.c) > 0.0));
+    }
+
+    #[test]
+    fn test_no_degenerate_curve_triangles() {
+        // The cubic has collinear control points, so its enclosing polygon has a zero-area fan triangle
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::CubicBezierTo(Vec2::new(1.0, -1.0), Vec2::new(2.0, -2.0), Vec2::new(4.0, 0.5)),
+            PathCommand::LineTo(Vec2::new(4.5, 4.0)),
+            PathCommand::LineTo(Vec2::new(-0.5, 4.2))
+        ];
+
+        let drawing = compile_path(&path, FillRule::NonZero);
+        assert!(!drawing.curve_triangles.is_empty());
+        assert!(drawing.curve_triangles.iter().all(|t| !t.is_degenerate()));
+        assert!(drawing.double_curve_triangles.iter().all(|t| !t.is_degenerate()));
+    }
+
+    #[test]
+    fn test_quantize() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.01, 0.02)),
+            PathCommand::QuadraticBezierTo(Vec2::new(2.03, -1.98), Vec2::new(4.02, 0.49)),
+            PathCommand::LineTo(Vec2::new(4.51, 3.99)),
+            PathCommand::LineTo(Vec2::new(0.03, 4.01)),
+            PathCommand::LineTo(Vec2::new(0.02, 4.0))
+        ];
+
+        let mut drawing = compile_path(&path, FillRule::NonZero);
+        let tex_before: Vec<_> = drawing.curve_triangles.iter().map(|t| t.a.tex).collect();
+        drawing.quantize(0.5);
+
+        let on_grid = |v: Vec2| (v.x * 2.0).fract() == 0.0 && (v.y * 2.0).fract() == 0.0;
+        assert!(drawing.triangles.iter().all(|t| on_grid(t.a) && on_grid(t.b) && on_grid(t.c)));
+        assert!(drawing.curve_triangles.iter().all(|t| on_grid(t.a.pos) && on_grid(t.b.pos) && on_grid(t.c.pos)));
+        assert!(drawing.triangles.iter().all(|t| !t.is_degenerate()));
+
+        // No curve triangle collapses on this grid, so they still line up with the ones before
+        assert_eq!(drawing.curve_triangles.len(), tex_before.len());
+        assert!(drawing.curve_triangles.iter().zip(tex_before).all(|(t, tex)| t.a.tex == tex));
+    }
+
+    #[test]
+    fn test_no_fusion() {
+        // The two curves meet at a sharp angle, so they are fused by default
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(2.0, 0.2), Vec2::new(4.0, 0.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(2.0, 0.1), Vec2::new(0.0, 0.0))
+        ];
+
+        let options = |fusion| CompileOptions { fusion, ..CompileOptions::default() };
+        let fused = compile_path_with(&path, FillRule::NonZero, &options(FusionMode::Auto)).unwrap();
+        assert!(!fused.double_curve_triangles.is_empty());
+
+        let plain = compile_path_with(&path, FillRule::NonZero, &options(FusionMode::Off)).unwrap();
+        assert!(plain.double_curve_triangles.is_empty());
+        assert_eq!(plain.curve_triangles.len(), 2);
+    }
+
+    #[test]
+    fn test_curve_subdivision() {
+        // The cubic has a sharp turn near its end
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::CubicBezierTo(Vec2::new(1.0, -2.0), Vec2::new(6.0, -2.0), Vec2::new(4.0, 0.5)),
+            PathCommand::LineTo(Vec2::new(4.5, 4.0)),
+            PathCommand::LineTo(Vec2::new(-0.5, 4.2))
+        ];
+
+        let face = || split_comps(simplify_curves(path_to_closed_curves(&path)), FillRule::NonZero).remove(0);
+        let num_cubics = |face: &FillFace| face.contours.iter().flatten().filter(|c| matches!(c, Curve::CubicBezier(_))).count();
+        let original = num_cubics(&face());
+
+        let subdivided = subdivide_by_arc_length(face(), CurveSubdiv::ByArcLength(4));
+        assert_eq!(num_cubics(&subdivided), 4 * original);
+        let lengths: Vec<_> = subdivided.contours.iter().flatten().filter(|c| !c.is_line()).map(|c| c.length()).collect();
+        for l in &lengths { assert!((l - lengths[0]).abs() < 1e-2 * lengths[0]); }
+
+        let options = CompileOptions { curve_subdivision: CurveSubdiv::ByArcLength(4), ..CompileOptions::default() };
+        let plain = compile_path(&path, FillRule::NonZero);
+        let fine = compile_path_with(&path, FillRule::NonZero, &options).unwrap();
+        assert!(fine.curve_triangles.len() > plain.curve_triangles.len());
+    }
+
+    #[test]
+    fn test_coincident_arc_endpoints() {
+        // M10 10 A5 5 0 0 0 10 10 in the middle of a square
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(10.0, 10.0)),
+            PathCommand::EllipticArcTo(Vec2::new(5.0, 5.0), 0.0, false, false, Vec2::new(10.0, 10.0)),
+            PathCommand::LineTo(Vec2::new(14.0, 10.0)),
+            PathCommand::LineTo(Vec2::new(14.0, 14.0)),
+            PathCommand::LineTo(Vec2::new(10.0, 14.0)),
+            PathCommand::ClosePath
+        ];
+
+        let drawing = compile_path(&path, FillRule::NonZero);
+        assert!(!drawing.triangles.is_empty());
+        assert!(drawing.triangles.iter().all(|t| t.a.is_finite() && t.b.is_finite() && t.c.is_finite()));
+        assert!(drawing.curve_triangles.is_empty());
+    }
+
+    #[test]
+    fn test_drawing_convex_hull() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(2.0, -2.0), Vec2::new(4.0, 0.5)),
+            PathCommand::LineTo(Vec2::new(4.5, 4.0)),
+            PathCommand::LineTo(Vec2::new(-0.5, 4.2))
+        ];
+
+        let hull = compile_path(&path, FillRule::NonZero).convex_hull();
+        let curve_hull = crate::curve::convex_hull_of_curves(&path_to_closed_curves(&path));
+        assert!(polygon_winding(&hull).roughly_equals(polygon_winding(&curve_hull)));
+    }
+
+    #[test]
+    fn test_build_adjacency() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 4.0)),
+            PathCommand::LineTo(Vec2::new(2.0, 5.0)),
+            PathCommand::LineTo(Vec2::new(0.0, 4.0))
+        ];
+
+        // A convex pentagon's triangulation is a manifold whose outline is the pentagon itself
+        let drawing = compile_path(&path, FillRule::NonZero);
+        let adjacency = drawing.build_adjacency();
+        assert_eq!(adjacency.vertices.len(), 5);
+        assert_eq!(adjacency.boundary_edges().len(), 5);
+        assert!(adjacency.edges.values().all(|t| t.len() <= 2));
+    }
+}
@@ -19,21 +19,17 @@ pub fn curve_vertices(curve: &Curve) -> ArrayVec<[CurveVertex; MAX_POLYGON_VERTI
             // The simplest case
             [
                 CurveVertex::new(q.a, Vec4::new(0.0, 0.0, 1.0, sign)),
-                CurveVertex::new(q.b, Vec4::new(0.0, 0.5, 1.0, sign)),
+                CurveVertex::new(q.b, Vec4::new(0.5, 0.0, 1.0, sign)),
                 CurveVertex::new(q.c, Vec4::new(1.0, 1.0, 1.0, sign))
             ].into_iter().copied().collect()
         },
         Curve::CubicBezier(c) => {
             // Use the computations in Chapter 4 of the Loop-Blinn paper
-            // The canonical form of the curve
-            let c3 = -c.a + 3.0 * c.b - 3.0 * c.c + c.d;
-            let c2 = 3.0 * c.a - 6.0 * c.b + 3.0 * c.c;
-            let c1 = -3.0 * c.a + 3.0 * c.b;
-
             // Again the inflection point polynomials, this time the homogeneous form
-            let d3 = c1.cross(c2);
-            let d2 = c3.cross(c1);
-            let d1 = c2.cross(c3);
+            let (d1, d2, d3) = c.inflection_coefficients();
+
+            // The orientation is flipped by negating k and l, which negates k³ - lm
+            let flip = |f: Vec4| Vec4::new(-f.x, -f.y, f.z, f.w);
 
             // The texture coordinates in canonical form
             let mut f0;
@@ -77,10 +73,10 @@ pub fn curve_vertices(curve: &Curve) -> ArrayVec<[CurveVertex; MAX_POLYGON_VERTI
 
                     // Guarantee that the signs are correct
                     if d1 < 0.0 {
-                        f0 = -f0;
-                        f1 = -f1;
-                        f2 = -f2;
-                        f3 = -f3;
+                        f0 = flip(f0);
+                        f1 = flip(f1);
+                        f2 = flip(f2);
+                        f3 = flip(f3);
                     }
                 } else {
                     // Loop
@@ -108,10 +104,10 @@ pub fn curve_vertices(curve: &Curve) -> ArrayVec<[CurveVertex; MAX_POLYGON_VERTI
                     let h = if h12.abs() > h.abs() { h12 } else { h };
 
                     if d1 * h > 0.0 {
-                        f0 = -f0;
-                        f1 = -f1;
-                        f2 = -f2;
-                        f3 = -f3;
+                        f0 = flip(f0);
+                        f1 = flip(f1);
+                        f2 = flip(f2);
+                        f3 = flip(f3);
                     }
                 }
             } else if d2 != 0.0 {
@@ -125,7 +121,7 @@ pub fn curve_vertices(curve: &Curve) -> ArrayVec<[CurveVertex; MAX_POLYGON_VERTI
                 f3 = Vec4::new(0.0, -1.0, 0.0, 0.0);
             } else if d3 != 0.0 {
                 // Degenerate forms for the cubic - a quadratic
-                f0 = Vec4::new(0.0, 0.0, 1.0, 0.0);
+                f0 = Vec4::new(0.0, 0.0, 0.0, 0.0);
                 f1 = Vec4::new(1.0, 0.0, 1.0, 0.0);
                 f2 = Vec4::new(0.0, 1.0, 0.0, 0.0);
                 f3 = Vec4::new(0.0, 0.0, 0.0, 0.0);
@@ -196,38 +192,18 @@ fn coord_extrapolator(vertices: &[CurveVertex], x: Vec2) -> Vec4 {
 
         // Extrapolate along a triangle
         _ => {
-            // Choose a non-zero area triangle
-            let mut i = 0;
-            let mut ik = 1;
-            let mut ik2 = 2;
-
-            while i < vertices.len() {
-                ik = (i+1) % vertices.len();
-                ik2 = (i+2) % vertices.len();
-
+            // Choose the first non-zero area triangle
+            let triangle = (0..len).map(|i| (i, (i+1) % len, (i+2) % len)).find(|&(i, ik, ik2)| {
                 let winding = vertices[i].pos.cross(vertices[ik].pos) +
                     vertices[ik].pos.cross(vertices[ik2].pos) +
                     vertices[ik2].pos.cross(vertices[i].pos);
+                !winding.roughly_zero_squared()
+            });
 
-                if winding.roughly_zero_squared() { break; }
-                i += 1;
-            }
-
-            // If there is no nonzero-area triangle, choose the most distant vertices and pick their extrapolator
-            if i == vertices.len() {
-                let mut imin = 0;
-                let mut imax = 0;
-
-                for i in 1..vertices.len() {
-                    if vertices[imin].pos.x > vertices[i].pos.x { imin = i; }
-                    if vertices[imax].pos.x < vertices[i].pos.x { imax = i; }
-                }
-
-                coord_extrapolator(&[vertices[imin], vertices[imax]], x)
-            } else {
+            if let Some((i, ik, ik2)) = triangle {
                 let a = vertices[i].pos;
-                let dv1 = vertices[ik].pos;
-                let dv2 = vertices[ik2].pos;
+                let dv1 = vertices[ik].pos - a;
+                let dv2 = vertices[ik2].pos - a;
                 let k = dv1.cross(dv2);
 
                 let ta = vertices[i].tex;
@@ -237,6 +213,17 @@ fn coord_extrapolator(vertices: &[CurveVertex], x: Vec2) -> Vec4 {
                 let u = (x - a).cross(dv2) / k;
                 let v = -(x - a).cross(dv1) / k;
                 ta + u * (tb - ta) + v * (tc - ta)
+            } else {
+                // If there is no nonzero-area triangle, choose the most distant vertices and pick their extrapolator
+                let mut imin = 0;
+                let mut imax = 0;
+
+                for i in 1..vertices.len() {
+                    if vertices[imin].pos.x > vertices[i].pos.x { imin = i; }
+                    if vertices[imax].pos.x < vertices[i].pos.x { imax = i; }
+                }
+
+                coord_extrapolator(&[vertices[imin], vertices[imax]], x)
             }
         }
     }
@@ -71,9 +71,20 @@ impl Face {
     fn outer() -> Face { Face { contours: Vec::new(), fill_number: 0, is_outer: true } }
 }
 
+// A lightweight snapshot of the DCEL, to observe what each of its phases does; only the faces
+// that still have contours (plus the outer face) are counted, along with their edges
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DcelSummary {
+    pub faces: usize,
+    pub contours: usize,
+    pub edges: usize,
+    // The fill numbers of the counted faces, in creation order
+    pub fill_numbers: Vec<isize>
+}
+
 #[derive(Debug)]
 pub struct Dcel {
-    vertices: Box<[Vertex]>,
+    vertices: Vec<Vertex>,
     edges: Vec<Edge>,
     faces: Vec<Face>
 }
@@ -107,15 +118,38 @@ impl Dcel {
 
     /// Create a new DCEL with the specified number of points
     pub fn new(num_pts: usize) -> Dcel {
+        Dcel::with_capacity(num_pts, 0)
+    }
+
+    /// Create a new DCEL with room for the expected number of edges (counting both twins)
+    pub fn with_capacity(num_pts: usize, expected_edges: usize) -> Dcel {
         let mut verts = Vec::with_capacity(num_pts);
         for _ in 0..num_pts { verts.push(Vertex::new()); }
 
         Dcel {
-            vertices: verts.into_boxed_slice(),
-            edges: Vec::new(), faces: vec![Face::outer()]
+            vertices: verts,
+            edges: Vec::with_capacity(expected_edges), faces: vec![Face::outer()]
         }
     }
 
+    /// Clear the DCEL so it can be built again with the specified number of points,
+    /// keeping the storage already allocated
+    pub fn reset(&mut self, num_pts: usize) {
+        self.reset_with_capacity(num_pts, 0);
+    }
+
+    /// Same as reset, but making room for the expected number of edges (counting both twins)
+    pub fn reset_with_capacity(&mut self, num_pts: usize, expected_edges: usize) {
+        self.vertices.truncate(num_pts);
+        for v in &mut self.vertices { v.out_edges.clear(); }
+        while self.vertices.len() < num_pts { self.vertices.push(Vertex::new()); }
+
+        self.edges.clear();
+        self.edges.reserve(expected_edges);
+        self.faces.clear();
+        self.faces.push(Face::outer());
+    }
+
     fn pair_of_edges(&mut self, curve: Curve) -> (usize, usize) {
         let len = self.edges.len();
         let rev = curve.reverse();
@@ -129,12 +163,14 @@ impl Dcel {
     }
 
     // This is for drain_filter down there
-    pub fn add_curve_canonicity(&mut self, v1: usize, v2: usize, curve: Curve, canonicity_change: isize) {
+    // Returns the edge (going the same way as the curve) whose canonicity was changed
+    pub fn add_curve_canonicity(&mut self, v1: usize, v2: usize, curve: Curve, canonicity_change: isize) -> usize {
         // Check if the vertices already have ongoing edges
         let found1 = !self.vertices[v1].out_edges.is_empty();
         let found2 = !self.vertices[v2].out_edges.is_empty();
 
         // Helpers
+        let credited;
         let ak1 = curve.angle_key();
         let ak2 = curve.reverse().angle_key();
         let p0 = curve.at(0.5);
@@ -156,6 +192,7 @@ impl Dcel {
             // The edge indices
             let (e1, e2) = self.pair_of_edges(curve);
             self.edges[e1].canonicity += canonicity_change;
+            credited = e1;
 
             // If the vertices are different, wire them on a loop
             if v1 != v2 {
@@ -179,21 +216,15 @@ impl Dcel {
                 let new_face = self.faces.len();
                 self.faces.push(Face::new());
 
-                // Select the convex edge, and add it to the new face
-                let (edge, twin) = if self.edges[e1].curve.winding() > 0.0 { (e1, e2) } else { (e2, e1) };
+                // Select the convex edge, and add it to the new face; the endpoints are only roughly
+                // equal, so the winding is taken about them, or the gap would be scaled by the distance
+                // to the origin and could flip the sign on small loops
+                let (edge, twin) = if self.edges[e1].curve.is_convex() { (e1, e2) } else { (e2, e1) };
                 self.faces[new_face].contours.push(edge);
                 self.edges[edge].face = new_face;
 
                 // Extract all the contours that should pertain to the new face
-                let (old_contours, mut new_contours): (Vec<_>, Vec<_>) 
-                    = self.faces[face].contours.iter().partition(|&&e| {
-                    self.face_contains_vertex(face, self.edges[e].curve.at(0.5))
-                });
-                self.faces[face].contours = old_contours;
-
-                // Add them to the new face
-                for c in &new_contours { self.assign_face(new_face, *c); }
-                self.faces[new_face].contours.append(&mut new_contours);
+                self.move_enclosed_contours(face, new_face);
 
                 // Add the concave edge to the outer face
                 self.faces[face].contours.push(twin);
@@ -211,6 +242,7 @@ impl Dcel {
             if let Some((e1lo, e1ro)) = self.vertices[v1].search_outgoing(ak1) {
                 let (e1, e2) = self.pair_of_edges(curve);
                 self.edges[e1].canonicity += canonicity_change;
+                credited = e1;
 
                 // The other matching edge is guaranteeded not to be found
                 let (e2lo, e2ro) = self.vertices[v2].search_outgoing(ak2).unwrap();
@@ -224,7 +256,7 @@ impl Dcel {
                 // There is a special case that needs to be handled for the same vertex
                 if v1 == v2 && e1lo == e2lo && e1ro == e2ro {
                     // Find the convex edge
-                    let (edge, twin) = if self.edges[e1].curve.winding() > 0.0 { (e1, e2) } else { (e2, e1) };
+                    let (edge, twin) = if self.edges[e1].curve.is_convex() { (e1, e2) } else { (e2, e1) };
                     self.edges[edge].next = edge;
                     self.edges[edge].prev = edge;
                     self.edges[t1ro].next = twin;
@@ -282,15 +314,7 @@ impl Dcel {
                     self.faces[new_face].contours.push(edge);
 
                     // Now, pluck all the old contours that should pertain to the new face
-                    let (old_contours, mut new_contours): (Vec<_>, Vec<_>) 
-                        = self.faces[old_face].contours.iter().partition(|&&e| {
-                        self.face_contains_vertex(old_face, self.edges[e].curve.at(0.5))
-                    });
-                    self.faces[old_face].contours = old_contours;
-
-                    // Add them to the new face
-                    for c in &new_contours { self.assign_face(new_face, *c); }
-                    self.faces[new_face].contours.append(&mut new_contours);
+                    self.move_enclosed_contours(old_face, new_face);
 
                     // Put the counterclockwise edge's twin in the old face
                     self.assign_face(old_face, twin);
@@ -300,12 +324,14 @@ impl Dcel {
                 // If a matching edge is found, we're done here, just up the canonicity of the edge
                 let e1 = self.vertices[v1].search(ak1).unwrap();
                 self.edges[e1].canonicity += canonicity_change;
+                credited = e1;
             }
         } else {
             // If only one of them is found, the case is very simple
             // Create the new pair of edges and set the right canonicity
             let (e1, e2) = self.pair_of_edges(curve);
             self.edges[e1].canonicity += canonicity_change;
+            credited = e1;
 
             // Let the new pair of edges according to the old vertex
             let (e1, e2) = if found1 { (e1, e2) } else { (e2, e1) };
@@ -337,6 +363,7 @@ impl Dcel {
         }
 
         self.print();
+        credited
     }
 
     // The test to see if an edge is (part of) a wedge
@@ -516,23 +543,100 @@ impl Dcel {
         self.print();
     }
 
-    // Get the face contours as a Vec of FillFaces
-    // I should return an enumerator, but I had enough fights with the compiler
-    // to give up this route
-    pub fn get_face_contours(self, fill_rule: FillRule) -> Vec<FillFace> {
-        let faces = (0..self.faces.len()).filter(|&fr| self.face_visible(fr, fill_rule));
-        faces.map(|fr| {
-            let iters = self.faces[fr].contours.iter();
-            let iters = iters.map(|&c| self.edge_loop_iter(c).map(|e| &self.edges[e].curve).cloned());
-            FillFace::new(iters)
-        }).collect()
+    pub fn summary(&self) -> DcelSummary {
+        let faces: Vec<_> = self.faces.iter().filter(|f| f.is_outer || !f.contours.is_empty()).collect();
+        let contours = faces.iter().map(|f| f.contours.len()).sum();
+        let edges = faces.iter().flat_map(|f| f.contours.iter())
+            .map(|&c| self.edge_loop_iter(c).count()).sum();
+        let fill_numbers = faces.iter().map(|f| f.fill_number).collect();
+        DcelSummary { faces: faces.len(), contours, edges, fill_numbers }
     }
 
-    fn face_visible(&self, face: usize, fill_rule: FillRule) -> bool {
-        match fill_rule {
-            FillRule::EvenOdd => self.faces[face].fill_number % 2 != 0,
-            FillRule::NonZero => self.faces[face].fill_number != 0
+    // Consume the DCEL, producing its visible faces one at a time
+    pub fn into_face_contours(self, fill_rule: FillRule) -> FaceContoursIterator {
+        FaceContoursIterator { dcel: self, fill_rule, face: 0 }
+    }
+
+    // Consume the DCEL, producing its visible faces along with the outer face, whose contours
+    // bound the whole drawing (running clockwise, since they are the twins of the faces' edges)
+    pub fn get_face_contours_including_outer(self, fill_rule: FillRule) -> (Vec<FillFace>, FillFace) {
+        let outer = self.fill_face(0);
+        (self.into_face_contours(fill_rule).collect(), outer)
+    }
+
+    // Consume the DCEL, producing its visible faces except the ones whose area is below min_area,
+    // like the slivers between curves that nearly overlap, along with how many were dropped
+    pub fn get_face_contours_filtered(self, fill_rule: FillRule, min_area: Coord) -> (Vec<FillFace>, usize) {
+        let visible = (0..self.faces.len()).filter(|&f| self.face_visible(f, fill_rule));
+        let (kept, dropped): (Vec<_>, Vec<_>) = visible.partition(|&f| self.face_area(f) >= min_area);
+        (kept.into_iter().map(|f| self.fill_face(f)).collect(), dropped.len())
+    }
+
+    // The area of the face, half the sum of the windings of its contours (the holes' being negative)
+    fn face_area(&self, face: usize) -> Coord {
+        let contours = self.faces[face].contours.iter().flat_map(|&c| self.edge_loop_iter(c));
+        contours.map(|e| self.edges[e].curve.winding()).sum::<Coord>() / 2.0
+    }
+
+    // The visible faces, in the same order as into_face_contours, but without consuming the DCEL
+    pub fn visible_faces(&self, fill_rule: FillRule) -> Vec<FillFace> {
+        (0..self.faces.len()).filter(|&f| self.face_visible(f, fill_rule)).map(|f| self.fill_face(f)).collect()
+    }
+
+    // Gather the faces for which the predicate holds, given the face indices
+    pub fn faces_where(&self, mut visible: impl FnMut(usize) -> bool) -> Vec<FillFace> {
+        (0..self.faces.len()).filter(|&f| !self.faces[f].contours.is_empty() && visible(f))
+            .map(|f| self.fill_face(f)).collect()
+    }
+
+    // The fill numbers each component gives to every face on its own, computed the same way
+    // as in assign_face_fill_numbers; the canonicities of each edge are given as pairs of
+    // component and canonicity, so an edge can belong to more than one component (and the edges
+    // past the end of the slice belong to none)
+    pub fn component_fill_numbers(&self, edge_components: &[Vec<(usize, isize)>], num_components: usize) -> Vec<Vec<isize>> {
+        let mut fill_numbers = vec![vec![0; num_components]; self.faces.len()];
+        let mut already_assigned_faces = empty_bool_vec(self.faces.len());
+        let mut iteration_queue = VecDeque::new();
+
+        // Start from the outer face, which is empty for every component
+        iteration_queue.push_back(0);
+        already_assigned_faces[0] = true;
+
+        while let Some(face) = iteration_queue.pop_front() {
+            for &c in &self.faces[face].contours {
+                for e in self.edge_loop_iter(c) {
+                    let t = self.edges[e].twin;
+                    let twin_face = self.edges[t].face;
+                    if already_assigned_faces[twin_face] { continue; }
+
+                    let mut fill = fill_numbers[face].clone();
+                    for &(comp, canonicity) in edge_components.get(e).into_iter().flatten() { fill[comp] -= canonicity; }
+                    for &(comp, canonicity) in edge_components.get(t).into_iter().flatten() { fill[comp] += canonicity; }
+                    fill_numbers[twin_face] = fill;
+                    iteration_queue.push_back(twin_face);
+                    already_assigned_faces[twin_face] = true;
+                }
+            }
         }
+
+        fill_numbers
+    }
+
+    fn fill_face(&self, face: usize) -> FillFace {
+        let iters = self.faces[face].contours.iter();
+        let iters = iters.map(|&c| self.edge_loop_iter(c).map(|e| &self.edges[e].curve).cloned());
+        FillFace::new(iters, self.faces[face].fill_number)
+    }
+
+    // Borrow the contours of the visible faces, face after face, without cloning their curves
+    pub fn face_contours_ref(&self, fill_rule: FillRule) -> impl Iterator<Item = impl Iterator<Item = &Curve> + '_> + '_ {
+        let faces = (0..self.faces.len()).filter(move |&f| self.face_visible(f, fill_rule));
+        faces.flat_map(move |f| self.faces[f].contours.iter())
+            .map(move |&c| self.edge_loop_iter(c).map(move |e| &self.edges[e].curve))
+    }
+
+    fn face_visible(&self, face: usize, fill_rule: FillRule) -> bool {
+        fill_rule.is_visible(self.faces[face].fill_number)
     }
 
     fn get_face_from_point(&self, v: Vec2) -> usize {
@@ -572,6 +676,21 @@ impl Dcel {
         return contains;
     }
 
+    // Move the contours of the old face that lie inside the new face's boundary (which must be
+    // its only contour so far); the old face's contours can't be tested against the old face
+    // itself, since their points lie on its own boundary
+    fn move_enclosed_contours(&mut self, old_face: usize, new_face: usize) {
+        let (mut new_contours, old_contours): (Vec<_>, Vec<_>)
+            = self.faces[old_face].contours.iter().partition(|&&e| {
+            self.face_contains_vertex(new_face, self.edges[e].curve.at(0.5))
+        });
+        self.faces[old_face].contours = old_contours;
+
+        // Add them to the new face
+        for c in &new_contours { self.assign_face(new_face, *c); }
+        self.faces[new_face].contours.append(&mut new_contours);
+    }
+
     fn assign_face(&mut self, face: usize, edge: usize) {
         // Safety: this function won't alter self.edges[_].next, that is needed for the iterator
         unsafe {
@@ -603,6 +722,37 @@ impl<'a> Iterator for EdgeLoopIterator<'a> {
     }
 }
 
+pub struct FaceContoursIterator {
+    dcel: Dcel,
+    fill_rule: FillRule,
+    face: usize
+}
+
+impl FaceContoursIterator {
+    // Give back the DCEL, so its storage can be reused with reset
+    pub fn into_dcel(self) -> Dcel { self.dcel }
+}
+
+impl Iterator for FaceContoursIterator {
+    type Item = FillFace;
+
+    fn next(&mut self) -> Option<FillFace> {
+        let dcel = &self.dcel;
+        while self.face < dcel.faces.len() {
+            let fr = self.face;
+            self.face += 1;
+
+            if dcel.face_visible(fr, self.fill_rule) { return Some(dcel.fill_face(fr)); }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.dcel.faces.len() - self.face))
+    }
+}
+
 struct UnsafeEdgeLoopIterator {
     edges: *const Edge,
     length: usize,
@@ -638,6 +788,7 @@ fn empty_bool_vec(sz: usize) -> Vec<bool> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test_paths::square;
 
     fn test_edge_iter(dcel: &Dcel, edge: usize, expected: &[usize]) {
         let lp: Vec<_> = dcel.edge_loop_iter(edge).collect();
@@ -646,7 +797,7 @@ mod test {
     }
 
     fn test_faces(dcel: &Dcel, expected: usize) {
-        assert!(dcel.faces.len() == expected, "Expected {} faces, but got {} faces", dcel.faces.len(), expected);
+        assert!(dcel.faces.len() == expected, "Expected {} faces, but got {} faces", expected, dcel.faces.len());
     }
 
     fn test_vertices(dcel: &Dcel, expected: &[usize]) {
@@ -683,6 +834,27 @@ mod test {
         test_vertices(&dcel, &[2, 2, 2]);
     }
 
+    #[test]
+    fn test_reset() {
+        let mut dcel = Dcel::with_capacity(3, 6);
+        dcel.add_curve(0, 1, Curve::line(Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0)));
+        dcel.add_curve(1, 2, Curve::line(Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0)));
+        dcel.add_curve(2, 0, Curve::line(Vec2::new(0.0, 1.0), Vec2::new(0.0, 0.0)));
+        let capacity = dcel.edges.capacity();
+
+        // Build a smaller DCEL on top of the same storage
+        dcel.reset(4);
+        test_faces(&dcel, 1);
+        test_vertices(&dcel, &[0, 0, 0, 0]);
+
+        dcel.add_curve(0, 3, Curve::line(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0)));
+        dcel.add_curve(3, 0, Curve::quadratic_bezier(Vec2::new(1.0, 1.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 0.0)));
+        test_edge_iter(&dcel, 0, &[0, 2]);
+        test_faces(&dcel, 2);
+        test_vertices(&dcel, &[2, 0, 0, 2]);
+        assert_eq!(dcel.edges.capacity(), capacity);
+    }
+
     #[test]
     fn test_double_face() {
         // Create the DCEL
@@ -721,5 +893,125 @@ mod test {
         test_vertices(&dcel, &[2, 3, 3, 2]);
     }
 
+    // Every edge must be on exactly one contour, whose face is the one recorded on the edge
+    fn test_bookkeeping(dcel: &Dcel) {
+        let mut seen = vec![false; dcel.edges.len()];
+        for (f, face) in dcel.faces.iter().enumerate() {
+            for &c in &face.contours {
+                for e in dcel.edge_loop_iter(c) {
+                    assert!(!seen[e], "Edge {} is on more than one contour", e);
+                    assert_eq!(dcel.edges[e].face, f, "Edge {} is on a contour of face {}", e, f);
+                    seen[e] = true;
+                }
+            }
+        }
+        assert!(seen.iter().all(|&s| s), "Some edges are not on any contour");
+    }
+
+    // A loop that sweeps just short of a full turn, since the arcs sweep less than one, so both of
+    // its ends are clustered into the same vertex
+    fn loop_arc(center: Vec2, radius: Coord, start: Vec2, ccw: bool) -> Curve {
+        let dt = if ccw { -(TWO_PI - 1e-6) } else { TWO_PI - 1e-6 };
+        Curve::EllipticArc(EllipticArc { center, radii: Vec2::new(radius, radius), crot: start.normalized(), t1: 0.0, dt })
+    }
+
+    fn fill_numbers(dcel: &mut Dcel) -> Vec<isize> {
+        dcel.assign_face_fill_numbers();
+        dcel.faces.iter().map(|f| f.fill_number).collect()
+    }
+
+    #[test]
+    fn test_full_circle() {
+        for &ccw in &[false, true] {
+            let mut dcel = Dcel::new(1);
+            dcel.add_curve(0, 0, loop_arc(Vec2::new(1.0, 0.0), 1.0, Vec2::new(-1.0, 0.0), ccw));
+            test_faces(&dcel, 2);
+            test_vertices(&dcel, &[2]);
+            test_bookkeeping(&dcel);
+
+            // The inner face is bounded by the counterclockwise edge
+            let inner = dcel.faces[1].contours[0];
+            assert!(dcel.edges[inner].curve.winding() > 0.0);
+            assert_eq!(fill_numbers(&mut dcel), vec![0, if ccw { -1 } else { 1 }]);
+        }
+    }
+
+    #[test]
+    fn test_small_loop_far_from_origin() {
+        // A counterclockwise loop that doesn't quite close, far enough for the gap to matter
+        let mut arc = loop_arc(Vec2::new(40000.0, 0.0), 0.01, Vec2::new(1.0, 0.0), false);
+        if let Curve::EllipticArc(a) = &mut arc { a.dt -= 1e-5; }
+        let mut dcel = Dcel::new(1);
+        dcel.add_curve(0, 0, arc);
+        test_bookkeeping(&dcel);
+        assert_eq!(dcel.faces[1].contours, vec![0]);
+        assert_eq!(fill_numbers(&mut dcel), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_full_circle_around_contour() {
+        // A triangle first, and then a circle around it that touches nothing
+        let mut dcel = Dcel::new(4);
+        let pts = [Vec2::new(10.0, 9.5), Vec2::new(10.5, 10.5), Vec2::new(9.5, 10.5)];
+        for i in 0..3 { dcel.add_curve(i, (i+1) % 3, Curve::line(pts[i], pts[(i+1) % 3])); }
+        dcel.add_curve(3, 3, loop_arc(Vec2::new(10.0, 10.0), 2.0, Vec2::new(0.0, -1.0), false));
+        test_faces(&dcel, 3);
+        test_bookkeeping(&dcel);
+
+        // The triangle's outside must now be inside the circle's face
+        let circle_face = dcel.edges[dcel.vertices[3].out_edges.values().copied()
+            .find(|&e| dcel.edges[e].curve.winding_at_midpoint() > 0.0).unwrap()].face;
+        assert_eq!(dcel.faces[circle_face].contours.len(), 2);
+        assert_eq!(dcel.faces[0].contours.len(), 1);
+        assert_eq!(fill_numbers(&mut dcel), vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_figure_eight() {
+        // Two lobes meeting at the origin, one on each side
+        let mut dcel = Dcel::new(1);
+        dcel.add_curve(0, 0, loop_arc(Vec2::new(1.0, 0.0), 1.0, Vec2::new(-1.0, 0.0), false));
+        dcel.add_curve(0, 0, loop_arc(Vec2::new(-1.0, 0.0), 1.0, Vec2::new(1.0, 0.0), true));
+        test_faces(&dcel, 3);
+        test_vertices(&dcel, &[4]);
+        test_bookkeeping(&dcel);
+
+        // The outer face wraps around both lobes with a single contour
+        assert_eq!(dcel.faces[0].contours.len(), 1);
+        assert_eq!(dcel.edge_loop_iter(dcel.faces[0].contours[0]).count(), 2);
+        for f in 1..3 {
+            assert_eq!(dcel.faces[f].contours.len(), 1);
+            assert_eq!(dcel.edge_loop_iter(dcel.faces[f].contours[0]).count(), 1);
+        }
+        assert_eq!(fill_numbers(&mut dcel), vec![0, 1, -1]);
+    }
+
+    #[test]
+    fn test_teardrop() {
+        // A cubic that leaves and comes back to the same point, at a sharp corner
+        let mut dcel = Dcel::new(1);
+        let teardrop = Curve::cubic_bezier(Vec2::new(0.0, 0.0), Vec2::new(3.0, -2.0), Vec2::new(3.0, 2.0), Vec2::new(0.0, 0.0));
+        dcel.add_curve(0, 0, teardrop.reverse());
+        test_faces(&dcel, 2);
+        test_bookkeeping(&dcel);
+        assert!(dcel.edges[dcel.faces[1].contours[0]].curve.winding() > 0.0);
+        assert_eq!(fill_numbers(&mut dcel), vec![0, -1]);
+    }
+
+    #[test]
+    fn test_face_contours_filtered() {
+        // Two squares that nearly coincide, leaving two thin L-shaped slivers around their overlap
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(square(1e-3, 1e-3, 2.0));
+
+        let all = crate::path::split_comps_dcel(curves.clone(), FillRule::NonZero).visible_faces(FillRule::NonZero);
+        assert_eq!(all.len(), 3);
+
+        let dcel = crate::path::split_comps_dcel(curves, FillRule::NonZero);
+        let (faces, dropped) = dcel.get_face_contours_filtered(FillRule::NonZero, 1e-2);
+        assert_eq!((faces.len(), dropped), (1, 2));
+        assert_eq!(faces[0].fill_number, 2);
+    }
+
     // TODO: add tests for each of the three "simplification" routines
 }
@@ -5,16 +5,184 @@
 //------------------------------------------------------------------------------
 
 use crate::curve::*;
+use crate::geometry::*;
+use super::{FillRule, ray_crossings};
+use std::cmp::Ordering;
+use crate::ordered_float::OrderedFloat;
+
+// The number of points each curve contributes to a contour's polygon when testing containment
+const CONTOUR_SAMPLES_PER_CURVE: usize = 8;
+
+// The role of a contour inside its face: the holes are the contours that are inside another
+// contour, which is their parent (the innermost one that contains them)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContourRole { Outer, Hole { parent: usize } }
 
 #[derive(Debug)]
-#[repr(transparent)]
 pub struct FillFace {
-    pub contours: Vec<Vec<Curve>>
+    pub contours: Vec<Vec<Curve>>,
+    // The winding number of the face, which decides its visibility under each fill rule
+    pub fill_number: isize
 }
 
 impl FillFace {
-    pub fn new(contours: impl Iterator<Item = impl Iterator<Item = Curve>>) -> FillFace {
+    pub fn new(contours: impl Iterator<Item = impl Iterator<Item = Curve>>, fill_number: isize) -> FillFace {
         let contours = contours.map(|c| c.collect()).collect();
-        FillFace { contours }
+        FillFace { contours, fill_number }
+    }
+
+    pub fn is_visible(&self, fill_rule: FillRule) -> bool { fill_rule.is_visible(self.fill_number) }
+
+    // Label each contour as an outer boundary or a hole; the contours of a face don't cross, so
+    // a point of one contour is enough to tell whether it is inside another one
+    pub fn classify_contours(&self) -> Vec<ContourRole> {
+        let polygons: Vec<_> = self.contours.iter().map(|c| contour_polygon(c)).collect();
+        let areas: Vec<_> = polygons.iter().map(|p| polygon_winding(p).abs()).collect();
+
+        (0..self.contours.len()).map(|i| {
+            let point = match self.contours[i].first() {
+                Some(curve) => curve.at(0.5),
+                None => return ContourRole::Outer
+            };
+
+            let containers: Vec<_> = (0..polygons.len())
+                .filter(|&j| j != i && polygon_contains_point(&polygons[j], point, true)).collect();
+
+            // A contour nested an even number of times is an outer boundary again
+            if containers.len() % 2 == 0 { ContourRole::Outer }
+            else {
+                let parent = containers.into_iter()
+                    .min_by(|&j, &k| areas[j].partial_cmp(&areas[k]).unwrap()).unwrap();
+                ContourRole::Hole { parent }
+            }
+        }).collect()
+    }
+
+    // Flatten each contour into a closed polyline (without repeating the first point at the end)
+    // oriented for extrusion: the outer boundaries go counterclockwise and the holes clockwise
+    pub fn boundary_loops(&self, tolerance: Coord) -> Vec<Vec<Vec2>> {
+        self.contours.iter().zip(self.classify_contours()).map(|(contour, role)| {
+            let mut points = Vec::new();
+            for curve in contour {
+                let flat = curve.flatten(tolerance);
+                points.extend_from_slice(&flat[..flat.len()-1]);
+            }
+
+            let ccw = role == ContourRole::Outer;
+            if (polygon_winding(&points) > 0.0) != ccw { points.reverse(); }
+            points
+        }).collect()
+    }
+
+    // Check whether the point is inside the face; the holes are contours too, so the point is
+    // inside if a ray from it crosses the contours an odd number of times
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        self.contours.iter().flatten().map(|c| ray_crossings(c, p)).sum::<isize>() % 2 != 0
+    }
+
+    // The lexicographically smallest vertex (by x, then y) of all the face's contours
+    pub fn min_vertex(&self) -> Option<Vec2> {
+        self.contours.iter().flatten().map(|c| c.at(0.0)).min_by(lexicographic_cmp)
+    }
+}
+
+// Flatten the contour into a polygon, sampling the curves that aren't lines
+fn contour_polygon(contour: &[Curve]) -> Vec<Vec2> {
+    let mut polygon = Vec::new();
+    for curve in contour {
+        if curve.is_line() { polygon.push(curve.at(0.0)); }
+        else {
+            let n = CONTOUR_SAMPLES_PER_CURVE;
+            polygon.extend((0..n).map(|i| curve.at(i as Coord / n as Coord)));
+        }
+    }
+    polygon
+}
+
+fn lexicographic_cmp(a: &Vec2, b: &Vec2) -> Ordering {
+    a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap())
+}
+
+// The index of the face that contains the point, if any; the faces coming out of the splitting
+// don't overlap, so there is at most one
+pub fn face_containing_point(faces: &[FillFace], p: Vec2) -> Option<usize> {
+    faces.iter().position(|f| f.contains_point(p))
+}
+
+// Sort the faces by their smallest vertices, so the same input always yields the same face order;
+// the faces that share it are ordered by the rest of their sorted vertices, and then by their fill
+// numbers, so the key is total. Each contour is rotated to start at its smallest vertex, and the
+// contours are sorted by it, so the faces themselves don't depend on the input order either
+pub fn sort_faces(faces: &mut [FillFace]) {
+    for face in faces.iter_mut() {
+        for contour in &mut face.contours {
+            let start = (0..contour.len()).min_by(|&i, &j| lexicographic_cmp(&contour[i].at(0.0), &contour[j].at(0.0)));
+            if let Some(start) = start { contour.rotate_left(start); }
+        }
+        face.contours.sort_by(|c1, c2| match (c1.first(), c2.first()) {
+            (Some(c1), Some(c2)) => lexicographic_cmp(&c1.at(0.0), &c2.at(0.0)),
+            (c1, c2) => c1.is_some().cmp(&c2.is_some())
+        });
     }
-}
\ No newline at end of file
+
+    faces.sort_by_cached_key(|f| {
+        let mut vertices: Vec<_> = f.contours.iter().flatten()
+            .map(|c| c.at(0.0)).map(|v| (OrderedFloat(v.x), OrderedFloat(v.y))).collect();
+        vertices.sort();
+        (vertices, f.fill_number)
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_paths::square;
+
+    #[test]
+    fn test_classify_contours() {
+        // A square with two holes, one of them holding an island with a circular hole
+        let circle = vec![
+            Curve::circle(Vec2::new(2.0, 2.0), 0.3, Vec2::new(1.0, 0.0), Vec2::new(-1.0, 0.0), true),
+            Curve::circle(Vec2::new(2.0, 2.0), 0.3, Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0), true)
+        ];
+        let contours = vec![square(1.5, 1.5, 1.0), square(0.0, 0.0, 10.0), circle, square(6.0, 6.0, 2.0), square(1.0, 1.0, 2.0)];
+        let face = FillFace { contours, fill_number: 1 };
+
+        assert_eq!(face.classify_contours(), vec![
+            ContourRole::Outer,
+            ContourRole::Outer,
+            ContourRole::Hole { parent: 0 },
+            ContourRole::Hole { parent: 1 },
+            ContourRole::Hole { parent: 1 }
+        ]);
+    }
+
+    #[test]
+    fn test_boundary_loops() {
+        // A clockwise square with a counterclockwise circular hole
+        let outer: Vec<_> = square(0.0, 0.0, 4.0).iter().rev().map(|c| c.reverse()).collect();
+        let circle = vec![
+            Curve::circle(Vec2::new(2.0, 2.0), 1.0, Vec2::new(1.0, 0.0), Vec2::new(-1.0, 0.0), false),
+            Curve::circle(Vec2::new(2.0, 2.0), 1.0, Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0), false)
+        ];
+        let face = FillFace { contours: vec![outer, circle], fill_number: 1 };
+
+        let loops = face.boundary_loops(1e-3);
+        assert_eq!(loops[0].len(), 4);
+        assert!(polygon_winding(&loops[0]) > 0.0);
+        assert!(polygon_winding(&loops[1]) < 0.0);
+        assert!((polygon_winding(&loops[1]).abs() / 2.0 - CoordM::consts::PI).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_face_containing_point() {
+        let face = |contours| FillFace { contours, fill_number: 1 };
+        let faces = vec![face(vec![square(0.0, 0.0, 4.0), square(1.0, 1.0, 2.0)]), face(vec![square(1.5, 1.5, 1.0)])];
+
+        assert_eq!(face_containing_point(&faces, Vec2::new(0.5, 2.0)), Some(0));
+        assert_eq!(face_containing_point(&faces, Vec2::new(2.0, 2.0)), Some(1));
+        // Inside the hole, but outside the island
+        assert_eq!(face_containing_point(&faces, Vec2::new(1.25, 2.0)), None);
+        assert_eq!(face_containing_point(&faces, Vec2::new(5.0, 2.0)), None);
+    }
+}
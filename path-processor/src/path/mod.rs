@@ -13,12 +13,29 @@ mod subdivision;
 mod compiled_drawing;
 mod curve_vertices;
 mod triangulation;
+mod validation;
+mod tessellation;
+mod adjacency;
+mod sdf;
+mod builder;
+mod clipping;
+mod binary_format;
 
 pub use splitting::*;
 pub use path_enums::*;
 pub use fill_face::*;
 pub use compiled_drawing::*;
 pub use subdivision_structs::*;
+pub use subdivision::{FusionParams, subdivide_by_arc_length};
+pub use validation::*;
+pub use adjacency::TriangleAdjacency;
+pub use sdf::generate_sdf;
+pub use builder::PathBuilder;
+pub use clipping::clip_to_rect;
+pub use binary_format::COMPILED_DRAWING_VERSION;
+pub use dcel::{Dcel, DcelSummary, FaceContoursIterator};
+pub use triangulation::{triangulate, triangulate_iter, triangulate_presimplified, triangulate_indexed,
+    triangulate_with_holes, triangulate_with_edge_flags};
 
 use std::fmt::*;
 
@@ -26,7 +43,7 @@ use crate::geometry::*;
 use crate::Curve;
 
 // The PathCommand enum
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum PathCommand {
     MoveTo(Vec2),
     LineTo(Vec2),
@@ -62,10 +79,235 @@ pub struct CurveComp {
     pub closed: bool
 }
 
+impl CurveComp {
+    // The bounding box of all the component's curves, if it has any
+    pub fn bbox(&self) -> Option<Rect> {
+        self.curves.iter().map(|c| c.bbox()).fold(None, |acc, r| Some(acc.map_or(r, |acc: Rect| acc.union(r))))
+    }
+
+    // Append the other component if it starts where this one ends, giving both back otherwise (or
+    // if either of them is already closed); the result is closed if it ends where it starts
+    pub fn try_join(mut self, mut other: CurveComp) -> std::result::Result<CurveComp, (CurveComp, CurveComp)> {
+        let meets = match (self.curves.last(), other.curves.first()) {
+            (Some(last), Some(first)) => last.at(1.0).roughly_equals(first.at(0.0)),
+            _ => false
+        };
+        if !meets || self.closed || other.closed { return Err((self, other)); }
+
+        self.curves.append(&mut other.curves);
+        self.closed = self.closing_line().is_none();
+        Ok(self)
+    }
+
+    // The chord that closes the component, if it doesn't end where it starts
+    fn closing_line(&self) -> Option<Curve> {
+        let p0 = self.curves.first()?.at(0.0);
+        let p1 = self.curves.last()?.at(1.0);
+        if p1.roughly_equals(p0) { None } else { Some(Curve::line(p1, p0)) }
+    }
+
+    // The sum of the winding integrals of the curves, without closing the component; for closed
+    // components, it is twice the enclosed area
+    pub fn winding_integral(&self) -> Coord { total_winding(&self.curves) }
+
+    // The area enclosed by the component (closed with a line if necessary), positive if counterclockwise
+    pub fn signed_area(&self) -> Coord {
+        let closing = self.closing_line().map_or(0.0, |c| c.winding());
+        (self.winding_integral() + closing) / 2.0
+    }
+
+    // The centroid of the enclosed area; it is not finite if the component encloses no area
+    pub fn centroid(&self) -> Vec2 {
+        let closing = self.closing_line();
+        let moments = self.curves.iter().chain(closing.iter()).fold(Vec2::zero(), |acc, c| acc + c.moments());
+        moments / (3.0 * self.signed_area())
+    }
+}
+
+// The sum of the winding integrals (∫ p × p' dt) of the curves, the quantity the fill numbers
+// come from; a closed contour is counterclockwise if it is positive
+pub fn total_winding(curves: &[Curve]) -> Coord {
+    curves.iter().map(|c| c.winding()).sum()
+}
+
+// Reverse a component, reversing each curve and the order of the curves
+pub fn reverse_comp(comp: &CurveComp) -> CurveComp {
+    CurveComp { curves: comp.curves.iter().rev().map(|c| c.reverse()).collect(), closed: comp.closed }
+}
+
+// Reverse a path, so each of its subpaths is traversed in the opposite direction
+pub fn reverse_path(path: &Path) -> Path {
+    let mut result = Vec::with_capacity(path.len() + 2);
+    let mut first_vec = Vec2::zero();
+    let mut prev_vec = Vec2::zero();
+
+    // The drawing commands of the current subpath, along with their starting points
+    let mut segments = Vec::new();
+
+    fn flush(result: &mut Path, segments: &mut Vec<(Vec2, PathCommand)>, first_vec: Vec2, prev_vec: Vec2, closed: bool) {
+        if segments.is_empty() { return; }
+
+        // A closed subpath starts at the same point, but goes through the closing line first
+        if closed {
+            result.push(PathCommand::MoveTo(first_vec));
+            if prev_vec != first_vec { result.push(PathCommand::LineTo(prev_vec)); }
+        } else { result.push(PathCommand::MoveTo(prev_vec)); }
+
+        for (from, cmd) in segments.drain(..).rev() {
+            result.push(match cmd {
+                PathCommand::LineTo(_) => PathCommand::LineTo(from),
+                PathCommand::QuadraticBezierTo(ctl, _) => PathCommand::QuadraticBezierTo(ctl, from),
+                PathCommand::CubicBezierTo(ctl1, ctl2, _) => PathCommand::CubicBezierTo(ctl2, ctl1, from),
+                // The arc keeps its size, but is swept in the other direction
+                PathCommand::EllipticArcTo(radii, angle, large_arc, sweep, _)
+                    => PathCommand::EllipticArcTo(radii, angle, large_arc, !sweep, from),
+                _ => unreachable!()
+            });
+        }
+
+        if closed { result.push(PathCommand::ClosePath); }
+    }
+
+    for cmd in path {
+        match cmd {
+            PathCommand::MoveTo(target) => {
+                flush(&mut result, &mut segments, first_vec, prev_vec, false);
+                first_vec = *target;
+                prev_vec = *target;
+            }
+            PathCommand::LineTo(target) | PathCommand::QuadraticBezierTo(_, target) |
+            PathCommand::CubicBezierTo(_, _, target) | PathCommand::EllipticArcTo(_, _, _, _, target) => {
+                segments.push((prev_vec, *cmd));
+                prev_vec = *target;
+            }
+            PathCommand::ClosePath => {
+                flush(&mut result, &mut segments, first_vec, prev_vec, true);
+                prev_vec = first_vec;
+            }
+        }
+    }
+
+    flush(&mut result, &mut segments, first_vec, prev_vec, false);
+    result
+}
+
+// The bounding box of all the curves of a path, without needing to compile it
+pub fn path_bbox(path: &Path) -> Option<Rect> {
+    path_to_curve_stream(path).map(|(c, _)| c.bbox()).fold(None, |acc, r| Some(acc.map_or(r, |acc: Rect| acc.union(r))))
+}
+
+// The winding number of the path around the point, casting a ray towards +x; the subpaths are
+// closed implicitly, as the fill does
+pub fn path_winding_number(path: &Path, p: Vec2) -> isize {
+    path_to_closed_curves(path).iter().map(|c| ray_crossings(c, p)).sum()
+}
+
+// The signed number of times the curve crosses the ray cast from the point towards +x, positive
+// when it goes up; summed over closed contours, it gives their winding number around the point
+pub(crate) fn ray_crossings(curve: &Curve, p: Vec2) -> isize {
+    let mut crossings = 0;
+
+    // Split the curve in pieces monotonous in y, each crossing the ray at most once; the pieces
+    // count their bottom endpoint but not the top one, so the shared vertices count only once
+    let roots = curve.intersection_y(p.y);
+    for (tl, tr) in curve.monotone_spans() {
+        let (yl, yr) = (curve.at(tl).y, curve.at(tr).y);
+        if (yl <= p.y) == (yr <= p.y) { continue; }
+
+        // Fall back to the middle of the piece if rounding threw the root off its range
+        let t = roots.as_ref().iter().copied().find(|&t| t >= tl && t <= tr).unwrap_or((tl + tr) / 2.0);
+        if curve.at(t).x > p.x { crossings += if yr > yl { 1 } else { -1 }; }
+    }
+
+    crossings
+}
+
+// Check whether the point is filled by the path, without needing to compile it
+pub fn path_contains_point(path: &Path, p: Vec2, fill_rule: FillRule) -> bool {
+    fill_rule.is_visible(path_winding_number(path, p))
+}
+
+// Mirror a path across the line that passes through axis_point with direction axis_dir
+pub fn mirror_path(path: &Path, axis_point: Vec2, axis_dir: Vec2) -> Path {
+    let r = |v: &Vec2| v.reflect(axis_point, axis_dir);
+    let axis_angle = axis_dir.angle();
+
+    path.iter().map(|cmd| match cmd {
+        PathCommand::MoveTo(target) => PathCommand::MoveTo(r(target)),
+        PathCommand::LineTo(target) => PathCommand::LineTo(r(target)),
+        PathCommand::QuadraticBezierTo(ctl, target) => PathCommand::QuadraticBezierTo(r(ctl), r(target)),
+        PathCommand::CubicBezierTo(ctl1, ctl2, target) => PathCommand::CubicBezierTo(r(ctl1), r(ctl2), r(target)),
+        // The ellipse's rotation is reflected as well, and the mirrored arc is swept in the other direction
+        PathCommand::EllipticArcTo(radii, angle, large_arc, sweep, target)
+            => PathCommand::EllipticArcTo(*radii, 2.0 * axis_angle - angle, *large_arc, !sweep, r(target)),
+        PathCommand::ClosePath => PathCommand::ClosePath
+    }).collect()
+}
+
+// Scale a path by the factors on each axis and then translate it; the arcs are scaled as the
+// ellipses they belong to, so their radii and rotation change together, and scales that mirror
+// the path sweep them in the other direction
+pub fn scale_path(path: &Path, scale: Vec2, translate: Vec2) -> Path {
+    let t = |v: &Vec2| Vec2::new(v.x * scale.x, v.y * scale.y) + translate;
+
+    path.iter().map(|cmd| match cmd {
+        PathCommand::MoveTo(target) => PathCommand::MoveTo(t(target)),
+        PathCommand::LineTo(target) => PathCommand::LineTo(t(target)),
+        PathCommand::QuadraticBezierTo(ctl, target) => PathCommand::QuadraticBezierTo(t(ctl), t(target)),
+        PathCommand::CubicBezierTo(ctl1, ctl2, target) => PathCommand::CubicBezierTo(t(ctl1), t(ctl2), t(target)),
+        PathCommand::EllipticArcTo(radii, angle, large_arc, sweep, target) => {
+            let (radii, angle) = scale_ellipse(*radii, *angle, scale);
+            PathCommand::EllipticArcTo(radii, angle, *large_arc, *sweep != (scale.x * scale.y < 0.0), t(target))
+        }
+        PathCommand::ClosePath => PathCommand::ClosePath
+    }).collect()
+}
+
+// The radii and rotation of the ellipse after scaling, found by decomposing the matrix that maps
+// the unit circle to the scaled ellipse as a rotation, a scaling and another rotation
+fn scale_ellipse(radii: Vec2, angle: Coord, scale: Vec2) -> (Vec2, Coord) {
+    let (sin, cos) = angle.sin_cos();
+    let (a, b) = (scale.x * radii.x * cos, -scale.x * radii.y * sin);
+    let (c, d) = (scale.y * radii.x * sin, scale.y * radii.y * cos);
+
+    let (e, f, g, h) = ((a + d) / 2.0, (a - d) / 2.0, (c + b) / 2.0, (c - b) / 2.0);
+    let (q, r) = ((e * e + h * h).sqrt(), (f * f + g * g).sqrt());
+    let angle = (g.atan2(f) + h.atan2(e)) / 2.0;
+    (Vec2::new(q + r, (q - r).abs()), angle)
+}
+
 pub fn path_to_curves(path: &Path) -> PathToCurvesIterator<'_> {
     PathToCurvesIterator { first_vec: Vec2::zero(), prev_vec: Vec2::zero(), path: path.iter() }
 }
 
+// Get all the curves of a path, closing each component with a line if needed
+pub fn path_to_closed_curves(path: &Path) -> Vec<Curve> {
+    path_to_curves_auto_close(path, AutoClose::Yes).unwrap()
+}
+
+// Get all the curves of a path, handling the components that don't end where they start
+// according to auto_close; the error lists the indices of those components
+pub fn path_to_curves_auto_close(path: &Path, auto_close: AutoClose) -> std::result::Result<Vec<Curve>, OpenSubpathError> {
+    let mut curves = Vec::new();
+    let mut open_subpaths = Vec::new();
+    for (i, mut comp) in path_to_curves(path).enumerate() {
+        let old_len = curves.len();
+        curves.append(&mut comp.curves);
+        let p0 = curves[old_len].at(0.0);
+        let p1 = curves[curves.len()-1].at(1.0);
+        if !p1.roughly_equals(p0) {
+            match auto_close {
+                AutoClose::Yes => curves.push(Curve::line(p1, p0)),
+                AutoClose::No => {},
+                AutoClose::Error => open_subpaths.push(i)
+            }
+        }
+    }
+
+    if open_subpaths.is_empty() { Ok(curves) }
+    else { Err(OpenSubpathError { subpaths: open_subpaths }) }
+}
+
 pub struct PathToCurvesIterator<'a> {
     first_vec: Vec2, prev_vec: Vec2,
     path: std::slice::Iter<'a, PathCommand>
@@ -87,23 +329,6 @@ impl<'a> Iterator for PathToCurvesIterator<'a> {
                         return Some(CurveComp { curves, closed: false });
                     }
                 }
-                PathCommand::LineTo(target) => {
-                    curves.push(Curve::line(self.prev_vec, *target));
-                    self.prev_vec = *target;
-                }
-                PathCommand::QuadraticBezierTo(ctl, target) => {
-                    curves.push(Curve::quadratic_bezier(self.prev_vec, *ctl, *target));
-                    self.prev_vec = *target;
-                }
-                PathCommand::CubicBezierTo(ctl1, ctl2, target) => {
-                    curves.push(Curve::cubic_bezier(self.prev_vec, *ctl1, *ctl2, *target));
-                    self.prev_vec = *target;
-                }
-                PathCommand::EllipticArcTo(radii, angle, large_arc, sweep, target) => {
-                    curves.push(Curve::elliptic_arc(self.prev_vec, *radii,
-                        *angle, *large_arc, *sweep, *target));
-                    self.prev_vec = *target;
-                }
                 PathCommand::ClosePath => {
                     if self.prev_vec != self.first_vec {
                         curves.push(Curve::line(self.prev_vec, self.first_vec));
@@ -114,10 +339,324 @@ impl<'a> Iterator for PathToCurvesIterator<'a> {
                         return Some(CurveComp { curves, closed: true });
                     }
                 }
+                _ => {
+                    let (curve, target) = command_curve(self.prev_vec, cmd);
+                    curves.extend(curve.split_full_turns());
+                    self.prev_vec = target;
+                }
             }
         }
     
         if !curves.is_empty() { Some(CurveComp { curves, closed: false }) }
         else { None }
     }
-}
\ No newline at end of file
+}
+
+// Build the curve a drawing command generates from the current point, along with its target
+fn command_curve(prev_vec: Vec2, cmd: &PathCommand) -> (Curve, Vec2) {
+    match cmd {
+        PathCommand::LineTo(target) => (Curve::line(prev_vec, *target), *target),
+        PathCommand::QuadraticBezierTo(ctl, target) =>
+            (Curve::quadratic_bezier(prev_vec, *ctl, *target), *target),
+        PathCommand::CubicBezierTo(ctl1, ctl2, target) =>
+            (Curve::cubic_bezier(prev_vec, *ctl1, *ctl2, *target), *target),
+        PathCommand::EllipticArcTo(radii, angle, large_arc, sweep, target) =>
+            (Curve::elliptic_arc(prev_vec, *radii, *angle, *large_arc, *sweep, *target), *target),
+        _ => unreachable!()
+    }
+}
+
+// Stream the curves of a path without grouping them in components; each curve comes
+// with a flag telling whether it starts a new component
+pub fn path_to_curve_stream(path: &Path) -> PathToCurveStreamIterator<'_> {
+    PathToCurveStreamIterator { first_vec: Vec2::zero(), prev_vec: Vec2::zero(), new_comp: true, path: path.iter() }
+}
+
+pub struct PathToCurveStreamIterator<'a> {
+    first_vec: Vec2, prev_vec: Vec2,
+    new_comp: bool,
+    path: std::slice::Iter<'a, PathCommand>
+}
+
+impl<'a> Iterator for PathToCurveStreamIterator<'a> {
+    type Item = (Curve, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for cmd in self.path.by_ref() {
+            match cmd {
+                PathCommand::MoveTo(target) => {
+                    self.first_vec = *target;
+                    self.prev_vec = *target;
+                    self.new_comp = true;
+                }
+                PathCommand::ClosePath => {
+                    let closing = if self.prev_vec != self.first_vec {
+                        Some((Curve::line(self.prev_vec, self.first_vec), self.new_comp))
+                    } else { None };
+
+                    // The component ends here, even if the path continues without a MoveTo
+                    self.prev_vec = self.first_vec;
+                    self.new_comp = true;
+                    if closing.is_some() { return closing; }
+                }
+                _ => {
+                    let (curve, target) = command_curve(self.prev_vec, cmd);
+                    let new_comp = std::mem::replace(&mut self.new_comp, false);
+                    self.prev_vec = target;
+                    return Some((curve, new_comp));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_curve_stream() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 0.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(6.0, 2.0), Vec2::new(4.0, 4.0)),
+            PathCommand::ClosePath,
+            PathCommand::LineTo(Vec2::new(-1.0, 0.0)),
+            PathCommand::MoveTo(Vec2::new(10.0, 0.0)),
+            PathCommand::MoveTo(Vec2::new(20.0, 0.0)),
+            PathCommand::CubicBezierTo(Vec2::new(21.0, 2.0), Vec2::new(23.0, -2.0), Vec2::new(24.0, 0.0))
+        ];
+
+        // The stream must have the same curves as the components, flagged on their boundaries
+        let stream: Vec<_> = path_to_curve_stream(&path).collect();
+        let comps = path_to_curves(&path).flat_map(|c| (0..c.curves.len()).map(|i| i == 0).zip(c.curves));
+
+        assert_eq!(stream.len(), 5);
+        for ((c1, new1), (new2, c2)) in stream.iter().zip(comps) {
+            assert!(c1.roughly_equals(&c2), "{} != {}", c1, c2);
+            assert_eq!(*new1, new2);
+        }
+    }
+
+    #[test]
+    fn test_reverse_path() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 0.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(6.0, 2.0), Vec2::new(4.0, 4.0)),
+            PathCommand::EllipticArcTo(Vec2::new(2.0, 1.0), 0.5, false, true, Vec2::new(0.0, 4.0)),
+            PathCommand::ClosePath,
+            PathCommand::MoveTo(Vec2::new(10.0, 0.0)),
+            PathCommand::CubicBezierTo(Vec2::new(11.0, 2.0), Vec2::new(13.0, -2.0), Vec2::new(14.0, 0.0))
+        ];
+
+        let reversed: Vec<_> = path_to_curves(&reverse_path(&path)).collect();
+        let expected: Vec<_> = path_to_curves(&path).map(|c| reverse_comp(&c)).collect();
+
+        assert_eq!(reversed.len(), expected.len());
+        for (r, e) in reversed.iter().zip(expected.iter()) {
+            assert_eq!(r.closed, e.closed);
+            assert_eq!(r.curves.len(), e.curves.len());
+            for (c1, c2) in r.curves.iter().zip(e.curves.iter()) {
+                assert!(c1.roughly_equals(c2), "{} != {}", c1, c2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mirror_path() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 0.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(6.0, 2.0), Vec2::new(4.0, 4.0)),
+            PathCommand::EllipticArcTo(Vec2::new(2.0, 1.0), 0.5, false, true, Vec2::new(0.0, 4.0)),
+            PathCommand::CubicBezierTo(Vec2::new(-1.0, 3.0), Vec2::new(1.0, 1.0), Vec2::new(-1.0, 1.0)),
+            PathCommand::ClosePath
+        ];
+        let (axis_point, axis_dir) = (Vec2::new(1.0, -2.0), Vec2::new(1.0, 3.0));
+
+        let mirrored = path_to_closed_curves(&mirror_path(&path, axis_point, axis_dir));
+        let expected = path_to_closed_curves(&path);
+
+        assert_eq!(mirrored.len(), expected.len());
+        for (c1, c2) in mirrored.iter().zip(expected.iter()) {
+            // The curve-level and the path-level mirroring must agree
+            let c2 = c2.mirror(axis_point, axis_dir);
+            assert!(c1.roughly_equals(&c2), "{} != {}", c1, c2);
+
+            // Each point must map to its reflection, and mirroring twice gives the same curve back
+            let c3 = c2.mirror(axis_point, axis_dir);
+            for i in 0..=8 {
+                let t = i as Coord / 8.0;
+                assert!(c1.at(t).reflect(axis_point, axis_dir).roughly_equals(c3.at(t)));
+            }
+        }
+
+        // Mirroring reverses the orientation of the path
+        let winding = |curves: &[Curve]| curves.iter().map(|c| c.winding()).sum::<Coord>();
+        assert!(winding(&mirrored).roughly_equals(-winding(&expected)));
+    }
+
+    #[test]
+    fn test_path_bbox() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(4.0, 0.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(6.0, 2.0), Vec2::new(4.0, 4.0)),
+            PathCommand::ClosePath,
+            PathCommand::MoveTo(Vec2::new(-2.0, 1.0)),
+            PathCommand::LineTo(Vec2::new(-1.0, -3.0))
+        ];
+
+        // The quadratic only reaches halfway to its control point
+        let bbox = path_bbox(&path).unwrap();
+        assert!(Vec2::new(bbox.x, bbox.y).roughly_equals(Vec2::new(-2.0, -3.0)));
+        assert!(Vec2::new(bbox.width, bbox.height).roughly_equals(Vec2::new(7.0, 7.0)));
+
+        let comps: Vec<_> = path_to_curves(&path).collect();
+        let bbox = comps[1].bbox().unwrap();
+        assert!(Vec2::new(bbox.x, bbox.y).roughly_equals(Vec2::new(-2.0, -3.0)));
+        assert!(Vec2::new(bbox.width, bbox.height).roughly_equals(Vec2::new(1.0, 4.0)));
+
+        assert!(path_bbox(&vec![PathCommand::MoveTo(Vec2::new(1.0, 1.0))]).is_none());
+    }
+
+    #[test]
+    fn test_auto_close() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(1.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(1.0, 1.0)),
+            PathCommand::ClosePath,
+            PathCommand::MoveTo(Vec2::new(2.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(3.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(3.0, 1.0))
+        ];
+
+        assert_eq!(path_to_curves_auto_close(&path, AutoClose::Yes).unwrap().len(), 6);
+        assert_eq!(path_to_curves_auto_close(&path, AutoClose::No).unwrap().len(), 5);
+        assert_eq!(path_to_curves_auto_close(&path, AutoClose::Error).unwrap_err().subpaths, vec![1]);
+        let options = CompileOptions { auto_close: AutoClose::Error, ..CompileOptions::default() };
+        assert!(compile_path_with(&path, FillRule::NonZero, &options).is_err());
+
+        // A subpath that comes back to its start doesn't need the ClosePath
+        let mut path = path[4..].to_vec();
+        path.push(PathCommand::LineTo(Vec2::new(2.0, 0.0)));
+        assert_eq!(path_to_curves_auto_close(&path, AutoClose::Error).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_area_and_centroid() {
+        // A half disk of radius 2 above the x axis, left open
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(2.0, 1.0)),
+            PathCommand::EllipticArcTo(Vec2::new(2.0, 2.0), 0.0, false, true, Vec2::new(-2.0, 1.0))
+        ];
+        let comp = path_to_curves(&path).next().unwrap();
+        assert!((comp.signed_area() - 2.0 * CoordM::consts::PI).abs() < 1e-9);
+        let expected = Vec2::new(0.0, 1.0 + 8.0 / (3.0 * CoordM::consts::PI));
+        assert!(comp.centroid().roughly_equals(expected));
+
+        // A clockwise triangle has negative area but the same centroid
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(0.0, 3.0)),
+            PathCommand::LineTo(Vec2::new(3.0, 0.0)),
+            PathCommand::ClosePath
+        ];
+        let comp = path_to_curves(&path).next().unwrap();
+        assert!((comp.signed_area() + 4.5).abs() < 1e-9);
+        assert!(comp.centroid().roughly_equals(Vec2::new(1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_path_contains_point() {
+        // Two overlapping squares with the same orientation, the second one left open, and a circle
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(2.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(2.0, 2.0)),
+            PathCommand::LineTo(Vec2::new(0.0, 2.0)),
+            PathCommand::ClosePath,
+            PathCommand::MoveTo(Vec2::new(1.0, 1.0)),
+            PathCommand::LineTo(Vec2::new(3.0, 1.0)),
+            PathCommand::LineTo(Vec2::new(3.0, 3.0)),
+            PathCommand::LineTo(Vec2::new(1.0, 3.0)),
+            PathCommand::MoveTo(Vec2::new(7.0, 1.0)),
+            PathCommand::EllipticArcTo(Vec2::new(1.0, 1.0), 0.0, false, true, Vec2::new(5.0, 1.0)),
+            PathCommand::EllipticArcTo(Vec2::new(1.0, 1.0), 0.0, false, true, Vec2::new(7.0, 1.0)),
+            PathCommand::ClosePath
+        ];
+
+        assert_eq!(path_winding_number(&path, Vec2::new(0.5, 0.5)), 1);
+        assert_eq!(path_winding_number(&path, Vec2::new(1.5, 1.5)), 2);
+        assert!(path_contains_point(&path, Vec2::new(1.5, 1.5), FillRule::NonZero));
+        assert!(!path_contains_point(&path, Vec2::new(1.5, 1.5), FillRule::EvenOdd));
+        assert!(path_contains_point(&path, Vec2::new(2.5, 2.5), FillRule::EvenOdd));
+        assert!(!path_contains_point(&path, Vec2::new(3.5, 2.5), FillRule::NonZero));
+
+        // The ray passes through the vertices of the squares and the extremes of the circle
+        assert!(path_contains_point(&path, Vec2::new(6.0, 1.0), FillRule::NonZero));
+        assert!(path_contains_point(&path, Vec2::new(5.5, 1.0), FillRule::NonZero));
+        assert!(!path_contains_point(&path, Vec2::new(-1.0, 1.0), FillRule::NonZero));
+        assert!(!path_contains_point(&path, Vec2::new(-1.0, 2.0), FillRule::NonZero));
+        assert!(!path_contains_point(&path, Vec2::new(4.0, 2.0), FillRule::NonZero));
+        assert!(!path_contains_point(&path, Vec2::new(-1.0, 0.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_try_join() {
+        let comp = |pts: &[Vec2]| CurveComp { curves: pts.windows(2).map(|w| Curve::line(w[0], w[1])).collect(), closed: false };
+        let (a, b, c) = (Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0));
+
+        let joined = comp(&[a, b]).try_join(comp(&[b, c])).ok().unwrap();
+        assert_eq!(joined.curves.len(), 2);
+        assert!(!joined.closed);
+
+        let closed = joined.try_join(comp(&[c, a])).ok().unwrap();
+        assert_eq!(closed.curves.len(), 3);
+        assert!(closed.closed);
+
+        // Components that don't meet, or are already closed, are given back untouched
+        let (first, second) = comp(&[a, b]).try_join(comp(&[c, a])).err().unwrap();
+        assert!(first.curves.len() == 1 && second.curves.len() == 1);
+        assert!(closed.try_join(comp(&[a, b])).is_err());
+    }
+
+    #[test]
+    fn test_winding_integral() {
+        let square = crate::test_paths::square(0.0, 0.0, 2.0);
+        assert!(total_winding(&square).roughly_equals(8.0));
+
+        let reversed = reverse_comp(&CurveComp { curves: square, closed: true });
+        assert!(reversed.winding_integral().roughly_equals(-8.0));
+        assert!(reversed.signed_area().roughly_equals(-4.0));
+    }
+
+    #[test]
+    fn test_scale_path() {
+        let path = vec![
+            PathCommand::MoveTo(Vec2::new(1.0, 0.0)),
+            PathCommand::EllipticArcTo(Vec2::new(2.0, 1.0), CoordM::consts::PI / 6.0, false, true, Vec2::new(-1.0, 1.0)),
+            PathCommand::QuadraticBezierTo(Vec2::new(0.0, 3.0), Vec2::new(1.0, 0.0))
+        ];
+
+        for &scale in &[Vec2::new(2.0, 2.0), Vec2::new(3.0, 0.5), Vec2::new(-1.0, 2.0)] {
+            let translate = Vec2::new(5.0, -1.0);
+            let original = path_to_closed_curves(&path);
+            let scaled = path_to_closed_curves(&scale_path(&path, scale, translate));
+            assert_eq!(original.len(), scaled.len());
+
+            // The scaled arc must be the image of the original one
+            for (c1, c2) in original.iter().zip(&scaled) {
+                for i in 0..=8 {
+                    let p = c1.at(i as Coord / 8.0);
+                    let p = Vec2::new(p.x * scale.x, p.y * scale.y) + translate;
+                    assert!(p.roughly_equals(c2.at(i as Coord / 8.0)), "{} {}", c2, scale);
+                }
+            }
+        }
+    }
+}
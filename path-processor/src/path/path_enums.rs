@@ -7,5 +7,37 @@
 
 use crate::derive_more::*;
 
-#[derive(Clone, Copy, Display, Debug)]
+#[derive(Clone, Copy, Display, Debug, PartialEq, Eq)]
 pub enum FillRule { EvenOdd, NonZero }
+
+impl FillRule {
+    // Check whether a region with the given fill number is filled under this rule
+    pub fn is_visible(self, fill_number: isize) -> bool {
+        match self {
+            FillRule::EvenOdd => fill_number % 2 != 0,
+            FillRule::NonZero => fill_number != 0
+        }
+    }
+}
+
+// What to do with the subpaths that don't end where they start: close them with a straight
+// line, keep them open (e.g. for stroking) or reject the path
+#[derive(Clone, Copy, Display, Debug, PartialEq, Eq)]
+pub enum AutoClose { Yes, No, Error }
+
+// What split_comps_with_policy does with the chains of curves that don't end where they start:
+// close them with a straight line, drop them or reject the input; unlike AutoClose, open chains
+// are never kept, as the fill numbers of their faces would be meaningless
+#[derive(Clone, Copy, Display, Debug, PartialEq, Eq)]
+pub enum OpenSubpathPolicy { Close, Ignore, Error }
+
+// Whether the compilation fuses the curves that meet at sharp angles into double curve triangles
+// (subdividing the overlapping ones first), or emits each curve on its own
+#[derive(Clone, Copy, Display, Debug, PartialEq, Eq)]
+pub enum FusionMode { Auto, Off }
+
+// How the cubics are split before building their curve triangles: not at all, or in the given
+// number of pieces of equal arc length, which gives more even triangles (and antialiasing) on
+// cubics with sharp sections at the cost of more triangles
+#[derive(Clone, Copy, Display, Debug, PartialEq, Eq)]
+pub enum CurveSubdiv { None, ByArcLength(usize) }
@@ -0,0 +1,56 @@
+//------------------------------------------------------------------------------
+// sdf.rs
+//------------------------------------------------------------------------------
+// Provides the generation of signed distance fields from the faces, for
+// rendering the shapes with crisp edges at any scale
+//------------------------------------------------------------------------------
+
+use crate::geometry::*;
+use super::FillFace;
+
+// The signed distance from the center of each cell of the grid laid over the bounds to the closest
+// contour curve, negative inside the faces and positive outside, in row-major order; the sign
+// comes from the containment test instead of the closest curve's side, which is ambiguous at
+// the corners of the contours
+pub fn generate_sdf(faces: &[FillFace], resolution: (usize, usize), bounds: Rect) -> Vec<f32> {
+    let (width, height) = resolution;
+    let cell = Vec2::new(bounds.width / width as Coord, bounds.height / height as Coord);
+    let curves: Vec<_> = faces.iter().flat_map(|f| f.contours.iter().flatten()).collect();
+
+    let mut sdf = Vec::with_capacity(width * height);
+    for j in 0..height {
+        for i in 0..width {
+            let p = Vec2::new(bounds.x + (i as Coord + 0.5) * cell.x, bounds.y + (j as Coord + 0.5) * cell.y);
+            let distance = curves.iter().map(|c| c.distance_to(p)).fold(Coord::INFINITY, Coord::min);
+            let inside = faces.iter().any(|f| f.contains_point(p));
+            sdf.push(if inside { -distance } else { distance } as f32);
+        }
+    }
+    sdf
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::curve::Curve;
+    use crate::path::FillRule;
+
+    #[test]
+    fn test_generate_sdf() {
+        // A circle of radius 2 at the center of a 8x8 grid of unit cells
+        let center = Vec2::new(4.0, 4.0);
+        let curves = vec![Curve::circle(center, 2.0, Vec2::new(1.0, 0.0), Vec2::new(-1.0, 0.0), false),
+            Curve::circle(center, 2.0, Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0), false)];
+        let faces = crate::path::split_comps(curves, FillRule::NonZero);
+
+        let sdf = generate_sdf(&faces, (8, 8), Rect::new(0.0, 0.0, 8.0, 8.0));
+        assert_eq!(sdf.len(), 64);
+        for j in 0..8 {
+            for i in 0..8 {
+                let p = Vec2::new(i as Coord + 0.5, j as Coord + 0.5);
+                let expected = (p - center).length() - 2.0;
+                assert!((sdf[j * 8 + i] as Coord - expected).abs() < 1e-4, "{} at {}", sdf[j * 8 + i], p);
+            }
+        }
+    }
+}
@@ -11,6 +11,7 @@ use crate::geometry::*;
 use crate::curve::*;
 use super::*;
 use crate::union_find::UnionFind;
+use super::dcel::{Dcel, DcelSummary, FaceContoursIterator};
 use std::collections::*;
 
 // Function to detect all the possible intersections
@@ -25,13 +26,283 @@ fn for_all_intersections<F>(curves: &[Curve], mut f: F)
     }
 }
 
+// Fold the curves that appear more than once (in either direction) into a single curve,
+// accumulating how many times each one appears under each key, a reversed appearance counting
+// negatively; a curve is kept as long as it doesn't cancel out under some key. The curves are
+// looked up by their coordinates snapped to the epsilon grid, so the merging takes linear time.
+// This is where the curves enter the splitting, so the arcs of a full turn or more are split here
+fn merge_duplicate_curves_by<K: Copy + PartialEq>(curves: impl ExactSizeIterator<Item = (Curve, K)>)
+    -> (Vec<Curve>, Vec<Vec<(K, isize)>>) {
+    let mut indices = HashMap::with_capacity(curves.len());
+    let mut merged: Vec<Curve> = Vec::with_capacity(curves.len());
+    let mut canonicities: Vec<Vec<(K, isize)>> = Vec::with_capacity(curves.len());
+
+    for (curve, key) in curves.flat_map(|(c, k)| c.split_full_turns().map(move |c| (c, k))) {
+        let (i, change) = if let Some(&i) = indices.get(&curve) { (i, 1) }
+        else if let Some(&i) = indices.get(&curve.reverse()) { (i, -1) }
+        else {
+            indices.insert(curve, merged.len());
+            merged.push(curve);
+            canonicities.push(Vec::new());
+            (merged.len() - 1, 1)
+        };
+
+        match canonicities[i].iter_mut().find(|(k, _)| *k == key) {
+            Some((_, k)) => *k += change,
+            None => canonicities[i].push((key, change))
+        }
+    }
+
+    for c in &mut canonicities { c.retain(|&(_, k)| k != 0); }
+    merged.into_iter().zip(canonicities).filter(|(_, c)| !c.is_empty()).unzip()
+}
+
+// How many times each curve appears on the original path
+fn merge_duplicate_curves(curves: Vec<Curve>) -> (Vec<Curve>, Vec<isize>) {
+    let (merged, canonicities) = merge_duplicate_curves_by(curves.into_iter().map(|c| (c, ())));
+    (merged, canonicities.into_iter().map(|c| c[0].1).collect())
+}
+
+// How many times each curve appears on each of the components
+fn merge_duplicate_curves_per_comp(curves: Vec<Curve>, components: &[usize])
+    -> (Vec<Curve>, Vec<Vec<(usize, isize)>>) {
+    merge_duplicate_curves_by(curves.into_iter().zip(components.iter().copied()))
+}
+
+// The faces are sorted by their smallest vertices, so the output is deterministic
 pub fn split_comps(curves: Vec<Curve>, fill_rule: FillRule) -> Vec<FillFace> {
+    let mut faces: Vec<_> = split_comps_iter(curves, fill_rule).collect();
+    sort_faces(&mut faces);
+    faces
+}
+
+// Same as split_comps, but handling the open chains of curves according to the policy; the
+// subpaths reported in the error are the indices of the chains, in input order
+pub fn split_comps_with_policy(curves: Vec<Curve>, fill_rule: FillRule, policy: OpenSubpathPolicy)
+    -> std::result::Result<Vec<FillFace>, OpenSubpathError> {
+    Ok(split_comps(apply_open_subpath_policy(curves, policy)?, fill_rule))
+}
+
+// Break the curves into chains, each one ending when it gets back to its start or when the next
+// curve doesn't start where the previous one ended, and apply the policy to the open ones
+fn apply_open_subpath_policy(curves: Vec<Curve>, policy: OpenSubpathPolicy)
+    -> std::result::Result<Vec<Curve>, OpenSubpathError> {
+    let mut result = Vec::with_capacity(curves.len());
+    let mut chain: Vec<Curve> = Vec::new();
+    let mut open_subpaths = Vec::new();
+    let mut num_chains = 0;
+
+    let mut curves = curves.into_iter().peekable();
+    while let Some(curve) = curves.next() {
+        chain.push(curve);
+
+        let (p0, p1) = (chain[0].at(0.0), curve.at(1.0));
+        let closed = p1.roughly_equals(p0);
+        if !closed && curves.peek().is_some_and(|c| c.at(0.0).roughly_equals(p1)) { continue; }
+
+        if !closed {
+            match policy {
+                OpenSubpathPolicy::Close => chain.push(Curve::line(p1, p0)),
+                OpenSubpathPolicy::Ignore => chain.clear(),
+                OpenSubpathPolicy::Error => open_subpaths.push(num_chains)
+            }
+        }
+
+        result.append(&mut chain);
+        num_chains += 1;
+    }
+
+    if open_subpaths.is_empty() { Ok(result) }
+    else { Err(OpenSubpathError { subpaths: open_subpaths }) }
+}
+
+// Same as split_comps, but the visible faces are extracted lazily as the iterator advances,
+// in the order they were created in the DCEL
+pub fn split_comps_iter(curves: Vec<Curve>, fill_rule: FillRule) -> impl Iterator<Item = FillFace> {
+    let mut dcel = build_dcel(curves, None);
+    dcel.simplify_faces(fill_rule);
+
+    // Return the visible faces
+    dcel.into_face_contours(fill_rule)
+}
+
+// Same as split_comps, but each component (each chain of curves, which ends when it gets back to
+// its start or when the next curve doesn't start where it ended) is filled with its own rule, the
+// components past the end of the rules being filled with the nonzero rule; a face is visible if
+// any of the components alone would make it visible
+pub fn split_comps_per_comp(curves: Vec<Curve>, rules: &[FillRule]) -> Vec<FillFace> {
+    let components = chain_indices(&curves);
+    let num_components = components.last().map_or(0, |&c| c + 1);
+    let (curves, canonicities) = merge_duplicate_curves_per_comp(curves, &components);
+    let totals = canonicities.iter().map(|c| c.iter().map(|&(_, k)| k).sum()).collect();
+
+    // Track the components each edge came from
+    let mut edge_components = Vec::new();
+    let mut scratch = SplitScratch::new();
+    insert_merged_curves(curves, totals, &mut scratch, |k, e| {
+        if edge_components.len() <= e { edge_components.resize(e + 1, Vec::new()); }
+        edge_components[e].extend_from_slice(&canonicities[k]);
+    });
+    let mut dcel = scratch.dcel;
+    dcel.remove_wedges();
+    dcel.assign_face_fill_numbers();
+
+    let fill_numbers = dcel.component_fill_numbers(&edge_components, num_components);
+
+    let rule = |c: usize| rules.get(c).copied().unwrap_or(FillRule::NonZero);
+    let mut faces = dcel.faces_where(|f| fill_numbers[f].iter().enumerate().any(|(c, &n)| rule(c).is_visible(n)));
+    sort_faces(&mut faces);
+    faces
+}
+
+// The index of the chain each curve belongs to, breaking the chains like apply_open_subpath_policy
+fn chain_indices(curves: &[Curve]) -> Vec<usize> {
+    let mut indices = Vec::with_capacity(curves.len());
+    let mut start = 0;
+    for i in 0..curves.len() {
+        indices.push(if i == 0 { 0 } else { indices[i-1] + (start == i) as usize });
+
+        let p1 = curves[i].at(1.0);
+        let closed = p1.roughly_equals(curves[start].at(0.0));
+        if closed || !curves.get(i+1).is_some_and(|c| c.at(0.0).roughly_equals(p1)) { start = i + 1; }
+    }
+    indices
+}
+
+// Same as split_comps, but also returning the outer face, the unfilled background around the
+// drawing; its contours make up the drawing's outer boundary, as for a clipping region
+pub fn split_comps_including_outer(curves: Vec<Curve>, fill_rule: FillRule) -> (Vec<FillFace>, FillFace) {
+    let (mut faces, outer) = split_comps_dcel(curves, fill_rule).get_face_contours_including_outer(fill_rule);
+    sort_faces(&mut faces);
+    (faces, outer)
+}
+
+// Same as split_comps_iter, but handing over the DCEL itself, so the contours of its visible
+// faces can be borrowed with face_contours_ref instead of being copied into FillFaces
+pub fn split_comps_dcel(curves: Vec<Curve>, fill_rule: FillRule) -> Dcel {
+    let mut dcel = build_dcel(curves, None);
+    dcel.simplify_faces(fill_rule);
+    dcel
+}
+
+// Same as split_comps_iter, but building the DCEL on the storage of a previous one; when
+// processing many paths, get it back with into_dcel to avoid allocating it again each time
+pub fn split_comps_reusing(curves: Vec<Curve>, fill_rule: FillRule, dcel: Dcel) -> FaceContoursIterator {
+    let mut dcel = build_dcel(curves, Some(dcel));
+    dcel.simplify_faces(fill_rule);
+    dcel.into_face_contours(fill_rule)
+}
+
+// The allocations split_comps makes on every call, which split_comps_with_scratch keeps between
+// calls instead, cleared but not freed
+pub struct SplitScratch {
+    dcel: Dcel,
+    critical_points: Vec<CriticalPoints>,
+    intersections: Vec<BTreeMap<OrderedFloat<Coord>, Vec2>>,
+    clusters: Vec<BTreeMap<OrderedFloat<Coord>, usize>>
+}
+
+impl Default for SplitScratch {
+    fn default() -> SplitScratch { SplitScratch::with_dcel(Dcel::new(0)) }
+}
+
+impl SplitScratch {
+    pub fn new() -> SplitScratch { SplitScratch::default() }
+
+    fn with_dcel(dcel: Dcel) -> SplitScratch {
+        SplitScratch { dcel, critical_points: Vec::new(), intersections: Vec::new(), clusters: Vec::new() }
+    }
+}
+
+// Same as split_comps, with the same output, but building everything on the storage of the
+// scratch, for when the curves are split again and again (like on every frame of an animation)
+pub fn split_comps_with_scratch(curves: Vec<Curve>, fill_rule: FillRule, scratch: &mut SplitScratch) -> Vec<FillFace> {
+    build_dcel_in(curves, scratch);
+    scratch.dcel.simplify_faces(fill_rule);
+
+    let mut faces = scratch.dcel.visible_faces(fill_rule);
+    sort_faces(&mut faces);
+    faces
+}
+
+// Split the curves keeping all the faces that are visible under any fill rule, so their
+// fill numbers can be used to switch between the rules without building the DCEL again
+pub fn split_comps_all(curves: Vec<Curve>) -> Vec<FillFace> {
+    let mut faces: Vec<_> = build_dcel(curves, None).into_face_contours(FillRule::NonZero).collect();
+    sort_faces(&mut faces);
+    faces
+}
+
+// The snapshots of the DCEL after each of its phases, along with the resulting faces
+#[derive(Debug)]
+pub struct SplitStages {
+    pub built: DcelSummary,
+    pub wedges_removed: DcelSummary,
+    pub fill_numbers_assigned: DcelSummary,
+    pub faces_simplified: DcelSummary,
+    pub faces: Vec<FillFace>
+}
+
+// Same as split_comps, but recording the state of the DCEL between its phases, to find out
+// which one of them is responsible for a bad fill
+pub fn split_comps_stepwise(curves: Vec<Curve>, fill_rule: FillRule) -> SplitStages {
+    let mut scratch = SplitScratch::new();
+    insert_curves(curves, &mut scratch);
+    let mut dcel = scratch.dcel;
+    let built = dcel.summary();
+    dcel.remove_wedges();
+    let wedges_removed = dcel.summary();
+    dcel.assign_face_fill_numbers();
+    let fill_numbers_assigned = dcel.summary();
+    dcel.simplify_faces(fill_rule);
+    let faces_simplified = dcel.summary();
+
+    let mut faces: Vec<_> = dcel.into_face_contours(fill_rule).collect();
+    sort_faces(&mut faces);
+    SplitStages { built, wedges_removed, fill_numbers_assigned, faces_simplified, faces }
+}
+
+// Build the DCEL of the curves, with the face fill numbers already assigned, reusing the
+// storage of the given DCEL if there is one
+fn build_dcel(curves: Vec<Curve>, storage: Option<Dcel>) -> Dcel {
+    let mut scratch = SplitScratch::with_dcel(storage.unwrap_or_else(|| Dcel::new(0)));
+    build_dcel_in(curves, &mut scratch);
+    scratch.dcel
+}
+
+// Same as build_dcel, but building the DCEL in the scratch, with its allocations
+fn build_dcel_in(curves: Vec<Curve>, scratch: &mut SplitScratch) {
+    insert_curves(curves, scratch);
+
+    // Do the DCEL simplification
+    scratch.dcel.remove_wedges();
+    scratch.dcel.assign_face_fill_numbers();
+}
+
+// Build the DCEL of the curves in the scratch, splitting them at their intersections, without
+// any of the later phases
+fn insert_curves(curves: Vec<Curve>, scratch: &mut SplitScratch) {
+    // Merge the duplicate curves first, so they don't generate sliver faces
+    let (curves, canonicities) = merge_duplicate_curves(curves);
+    insert_merged_curves(curves, canonicities, scratch, |_, _| ())
+}
+
+// Insert the curves, already without duplicates, calling back with the index of each curve
+// and the edge that each of its pieces credited its canonicity to
+fn insert_merged_curves<F>(curves: Vec<Curve>, canonicities: Vec<isize>, scratch: &mut SplitScratch, mut on_edge: F)
+    where F: FnMut(usize, usize) {
+    let SplitScratch { dcel, critical_points, intersections, clusters } = scratch;
+    if curves.is_empty() { return dcel.reset(0); }
+
     // Cache the curve's critical points
-    let critical_points: Vec<_> = curves.iter().map(|c| c.critical_points()).collect();
+    critical_points.clear();
+    critical_points.extend(curves.iter().map(|c| c.critical_points()));
 
+    #[cfg(feature = "debug_subdivision")]
     let then = std::time::Instant::now();
+
     // First, get all intersection points in the curve
-    let mut intersections = vec![BTreeMap::<OrderedFloat<_>, _>::new(); curves.len()];
+    reset_maps(intersections, curves.len());
     for_all_intersections(curves.as_slice(), |i1, i2| {
         let ints = intersection(&curves[i1], &curves[i2], &critical_points[i1], &critical_points[i2]);
         for int in ints {
@@ -51,75 +322,404 @@ pub fn split_comps(curves: Vec<Curve>, fill_rule: FillRule) -> Vec<FillFace> {
     }
 
     // Cluster the intersections and add them to the dcel
-    let (clusters, num_pts) = derive_clusters(&intersections);
-    let now = std::time::Instant::now();
-    println!("Time spent in the intersection: {:?}", now.duration_since(then));
+    let num_pts = derive_clusters(intersections, clusters);
+
+    #[cfg(feature = "debug_subdivision")]
+    {
+        let now = std::time::Instant::now();
+        println!("Time spent in the intersection: {:?}", now.duration_since(then));
+    }
 
     //let then = std::time::Instant::now();
-    let mut dcel = super::dcel::Dcel::new(num_pts);
-    for (curve, cluster) in curves.into_iter().zip(clusters.into_iter()) {
-        let cluster: Vec<_> = cluster.into_iter().collect();
+    // Each curve piece adds a pair of edges
+    let num_edges = clusters.iter().map(|c| 2 * (c.len() - 1)).sum();
+    dcel.reset_with_capacity(num_pts, num_edges);
+    let iter = curves.into_iter().zip(clusters.iter()).zip(canonicities);
+    for (k, ((curve, cluster), canonicity)) in iter.enumerate() {
+        let cluster: Vec<_> = cluster.iter().map(|(&t, &v)| (t, v)).collect();
         if cluster.len() == 2 {
             if !is_curve_degenerate(&curve) {
-                dcel.add_curve(cluster[0].1, cluster[1].1, curve);
+                on_edge(k, dcel.add_curve_canonicity(cluster[0].1, cluster[1].1, curve, canonicity));
             }
         } else {
             for i in 1..cluster.len() {
                 // Skip degenerate curves
                 let curve = curve.subcurve(*cluster[i-1].0, *cluster[i].0);
                 if !is_curve_degenerate(&curve) {
-                    dcel.add_curve(cluster[i-1].1, cluster[i].1, curve);
+                    on_edge(k, dcel.add_curve_canonicity(cluster[i-1].1, cluster[i].1, curve, canonicity));
                 }
             }
         }
     }
 
-    // Do the DCEL simplification
-    dcel.remove_wedges();
-    dcel.assign_face_fill_numbers();
-    dcel.simplify_faces(fill_rule);
     //let now = std::time::Instant::now();
     //println!("Time spent in the DCEL: {:?}", now.duration_since(then));
+}
 
-    // Return the visible faces
-    dcel.get_face_contours(fill_rule)
+// Clear the maps, keeping the vector's allocation, and make it hold len of them
+fn reset_maps<K: Ord, V>(maps: &mut Vec<BTreeMap<K, V>>, len: usize) {
+    maps.truncate(len);
+    for map in maps.iter_mut() { map.clear(); }
+    maps.resize_with(len, BTreeMap::new);
 }
 
-fn derive_clusters(intersections: &Vec<BTreeMap<OrderedFloat<Coord>, Vec2>>)
-    -> (Vec<BTreeMap<OrderedFloat<Coord>, usize>>, usize) {
+// Attribute the cluster of each intersection point to the curves, returning the number of clusters;
+// the clusters are numbered in the order their first points are found, going through the curves
+// and then their parameters, so the same curves always give the same DCEL vertices
+fn derive_clusters(intersections: &[BTreeMap<OrderedFloat<Coord>, Vec2>],
+    clusters: &mut Vec<BTreeMap<OrderedFloat<Coord>, usize>>) -> usize {
     // First, gather all points and create the union find
     let all_points: Vec<_> = intersections.iter().flat_map(|map| map.values()).collect();
     let mut uf = UnionFind::new(all_points.len());
 
     // Now, reunite the clusters
-    for i in 0..all_points.len()-1 {
-        for j in (i+1)..all_points.len() {
-            if all_points[i].roughly_equals(*all_points[j]) {
-                uf.union(i, j);
-            }
-        }
-    }
+    cluster_points(&all_points, &mut uf);
 
-    // "Flatten" the cluster parents
-    let mut flat = HashMap::new();
-    let mut max = 0;
-    for i in 0..all_points.len() {
-        if flat.get(&uf.find(i)).is_none() {
-            flat.insert(uf.find(i), max);
-            max += 1;
-        }
+    // "Flatten" the cluster parents, in first-encounter order, since the components are ordered by
+    // their smallest indices (the spatial hash grid is only ever looked up, never iterated)
+    let components = uf.components();
+    let mut flat = vec![0; all_points.len()];
+    for (c, component) in components.iter().enumerate() {
+        for &i in component { flat[i] = c; }
     }
 
     // Finally, attribute the (flattened) clusters to the original curves
-    let mut clusters = vec![BTreeMap::new(); intersections.len()];
+    reset_maps(clusters, intersections.len());
     let mut k = 0;
 
     for i in 0..intersections.len() {
         for (t, _) in &intersections[i] {
-            clusters[i].insert(*t, *flat.get(&uf.find(k)).unwrap());
+            clusters[i].insert(*t, flat[k]);
             k += 1;
         }
     }
 
-    (clusters, max)
+    components.len()
+}
+
+// Unite all the points that are roughly equal; the points are bucketed in a grid whose cells have
+// the size of the tolerance, so only the points in neighboring cells need to be compared
+fn cluster_points(points: &[&Vec2], uf: &mut UnionFind) {
+    let cell_of = |v: &Vec2| ((v.x / EPSILON).floor() as i64, (v.y / EPSILON).floor() as i64);
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+
+    for (i, p) in points.iter().enumerate() {
+        let (cx, cy) = cell_of(p);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if let Some(others) = grid.get(&(cx + dx, cy + dy)) {
+                    for &j in others {
+                        if p.roughly_equals(*points[j]) { uf.union(i, j); }
+                    }
+                }
+            }
+        }
+        grid.entry((cx, cy)).or_default().push(i);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_paths::square;
+
+    #[test]
+    fn test_full_turn_arcs_are_split() {
+        // A single arc sweeping a full turn, and then one sweeping two of them
+        for &turns in &[1.0, 2.0] {
+            let arc = EllipticArc { center: Vec2::new(1.0, 1.0), radii: Vec2::new(1.0, 1.0), crot: Vec2::new(1.0, 0.0),
+                t1: 0.0, dt: turns * TWO_PI };
+            let faces = split_comps(vec![Curve::EllipticArc(arc)], FillRule::NonZero);
+            assert_eq!(faces.len(), 1);
+            assert!(faces[0].contains_point(Vec2::new(1.0, 1.0)) && faces[0].contains_point(Vec2::new(1.9, 1.0)));
+            assert!(!faces[0].contains_point(Vec2::new(2.1, 1.0)));
+            assert_eq!(faces[0].fill_number, turns as isize);
+        }
+    }
+
+    #[test]
+    fn test_merge_duplicate_curves() {
+        let line = Curve::line(Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0));
+        let quad = Curve::quadratic_bezier(Vec2::new(1.0, 0.0), Vec2::new(2.0, 1.0), Vec2::new(1.0, 2.0));
+        let arc = Curve::circle(Vec2::new(0.0, 1.0), 1.0, Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0), true);
+
+        // Exact duplicates add up, reversed ones subtract, and a curve and its reverse cancel out
+        let nudged = Curve::line(Vec2::new(1e-9, 0.0), Vec2::new(1.0, -1e-9));
+        assert!(nudged.roughly_equals(&line));
+        let curves = vec![line, quad.reverse(), arc, line, quad, quad, arc.reverse(), nudged];
+        let (merged, canonicities) = merge_duplicate_curves(curves);
+
+        assert_eq!(merged.len(), 2);
+        assert!(merged[0].roughly_equals(&line) && merged[1].roughly_equals(&quad.reverse()));
+        assert_eq!(canonicities, vec![3, -1]);
+    }
+
+    #[test]
+    fn test_cluster_points_against_brute_force() {
+        // A small linear congruential generator, so the test is deterministic
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut random = move || {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 11) as Coord / (1u64 << 53) as Coord
+        };
+
+        for _ in 0..20 {
+            // Pick a few centers and scatter the points around them, some within the tolerance
+            let centers: Vec<_> = (0..8).map(|_| Vec2::new(random(), random())).collect();
+            let points: Vec<_> = (0..200).map(|i| {
+                let spread = if i % 2 == 0 { EPSILON } else { 4.0 * EPSILON };
+                centers[i % centers.len()] + spread * Vec2::new(random() - 0.5, random() - 0.5)
+            }).collect();
+            let points: Vec<_> = points.iter().collect();
+
+            let mut grid = UnionFind::new(points.len());
+            cluster_points(&points, &mut grid);
+
+            let mut brute = UnionFind::new(points.len());
+            for i in 0..points.len() {
+                for j in i+1..points.len() {
+                    if points[i].roughly_equals(*points[j]) { brute.union(i, j); }
+                }
+            }
+
+            assert_eq!(grid.components(), brute.components());
+        }
+    }
+
+    #[test]
+    fn test_derive_clusters_order() {
+        let (a, b, c, d) = (Vec2::new(3.0, 0.0), Vec2::new(0.0, 1.0), Vec2::new(2.0, 2.0), Vec2::new(-1.0, 5.0));
+        let map = |pts: &[(Coord, Vec2)]| pts.iter().map(|&(t, p)| (t.into(), p)).collect::<BTreeMap<_, _>>();
+        let intersections = vec![
+            map(&[(0.0, a), (0.5, b + Vec2::new(EPSILON / 4.0, 0.0)), (1.0, c)]),
+            map(&[(0.0, c), (0.25, d), (1.0, b)]),
+            map(&[(0.0, d), (1.0, a)])
+        ];
+
+        // Every run gives the clusters the indices of the first time they appear
+        for _ in 0..10 {
+            let mut clusters = Vec::new();
+            assert_eq!(derive_clusters(&intersections, &mut clusters), 4);
+            let indices: Vec<Vec<usize>> = clusters.iter().map(|c| c.values().copied().collect()).collect();
+            assert_eq!(indices, vec![vec![0, 1, 2], vec![2, 3, 1], vec![3, 0]]);
+        }
+    }
+
+    #[test]
+    fn test_fill_numbers() {
+        // Two overlapping squares with the same orientation
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(square(1.0, 1.0, 2.0));
+
+        let faces = split_comps_all(curves);
+        let mut fill_numbers: Vec<_> = faces.iter().map(|f| f.fill_number.abs()).collect();
+        fill_numbers.sort();
+        assert_eq!(fill_numbers, vec![1, 1, 2]);
+
+        // The intersection is only visible under the nonzero rule
+        assert_eq!(faces.iter().filter(|f| f.is_visible(FillRule::EvenOdd)).count(), 2);
+        assert_eq!(faces.iter().filter(|f| f.is_visible(FillRule::NonZero)).count(), 3);
+    }
+
+    #[test]
+    fn test_deterministic_face_order() {
+        // Feeding the curves in a different order must not change the faces nor their order
+        let assert_same_faces = |faces: &[FillFace], expected: &[FillFace]| {
+            assert_eq!(faces.len(), expected.len());
+            for (f, e) in faces.iter().zip(expected) {
+                assert_eq!(f.fill_number, e.fill_number);
+                assert_eq!(f.contours.len(), e.contours.len());
+                for (c, d) in f.contours.iter().zip(&e.contours) {
+                    assert_eq!(c.len(), d.len());
+                    assert!(c.iter().zip(d).all(|(c, d)| c.roughly_equals(d)), "{:?} {:?}", c, d);
+                }
+            }
+        };
+
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(square(1.0, 1.0, 2.0));
+        curves.extend(square(5.0, 0.0, 2.0));
+        let expected = split_comps_all(curves.clone());
+        curves.rotate_left(5);
+        assert_same_faces(&split_comps_all(curves), &expected);
+
+        let min_vertices: Vec<_> = expected.iter().map(|f| f.min_vertex().unwrap()).collect();
+        for w in min_vertices.windows(2) {
+            assert!((w[0].x, w[0].y) <= (w[1].x, w[1].y));
+        }
+
+        // Two triangles of opposite orientations, whose faces share their smallest vertex, so they
+        // are ordered by their other vertices
+        let (a, b, c, d) = (Vec2::new(5.0, 0.0), Vec2::new(7.0, 0.0), Vec2::new(7.0, 2.0), Vec2::new(5.0, 2.0));
+        let mut curves = vec![Curve::line(a, b), Curve::line(b, c), Curve::line(c, a),
+            Curve::line(a, d), Curve::line(d, c), Curve::line(c, a)];
+        let expected = split_comps_all(curves.clone());
+        assert_eq!(expected.iter().map(|f| f.fill_number).collect::<Vec<_>>(), vec![-1, 1]);
+        for _ in 0..curves.len() {
+            curves.rotate_left(1);
+            assert_same_faces(&split_comps_all(curves.clone()), &expected);
+        }
+    }
+
+    #[test]
+    fn test_split_comps_reusing() {
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(square(1.0, 1.0, 2.0));
+
+        // Build a bigger DCEL first, then reuse it for a smaller one
+        let faces = split_comps_reusing(curves.clone(), FillRule::NonZero, Dcel::new(0));
+        assert_eq!(faces.count(), split_comps_iter(curves.clone(), FillRule::NonZero).count());
+
+        let mut faces = split_comps_reusing(curves, FillRule::NonZero, Dcel::new(0));
+        faces.next();
+        let faces = split_comps_reusing(square(5.0, 0.0, 2.0), FillRule::NonZero, faces.into_dcel());
+        let faces: Vec<_> = faces.collect();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].min_vertex().unwrap(), Vec2::new(5.0, 0.0));
+    }
+
+    #[test]
+    fn test_split_comps_with_scratch() {
+        let mut overlapping = square(0.0, 0.0, 2.0);
+        overlapping.extend(square(1.0, 1.0, 2.0));
+        overlapping.extend(square(0.5, 1.5, 2.0));
+
+        // Alternate between bigger and smaller inputs, including an empty one, on the same scratch
+        let mut scratch = SplitScratch::new();
+        for curves in &[overlapping.clone(), square(5.0, 0.0, 2.0), Vec::new(), overlapping] {
+            for &rule in &[FillRule::NonZero, FillRule::EvenOdd] {
+                let faces = split_comps_with_scratch(curves.clone(), rule, &mut scratch);
+                let expected = split_comps(curves.clone(), rule);
+
+                assert_eq!(faces.len(), expected.len());
+                for (f, e) in faces.iter().zip(&expected) {
+                    assert_eq!(f.fill_number, e.fill_number);
+                    assert_eq!(f.contours.len(), e.contours.len());
+                    for (c, d) in f.contours.iter().flatten().zip(e.contours.iter().flatten()) {
+                        assert!(c.roughly_equals(d), "{} {}", c, d);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_subpath_policy() {
+        // A closed square followed by an open zigzag that crosses it
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.push(Curve::line(Vec2::new(1.0, 1.0), Vec2::new(3.0, 1.0)));
+        curves.push(Curve::line(Vec2::new(3.0, 1.0), Vec2::new(3.0, 3.0)));
+
+        let faces = split_comps_with_policy(curves.clone(), FillRule::EvenOdd, OpenSubpathPolicy::Ignore).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].contours[0].len(), 4);
+
+        let faces = split_comps_with_policy(curves.clone(), FillRule::EvenOdd, OpenSubpathPolicy::Close).unwrap();
+        assert_eq!(faces.len(), 2);
+
+        let error = split_comps_with_policy(curves, FillRule::EvenOdd, OpenSubpathPolicy::Error).unwrap_err();
+        assert_eq!(error.subpaths, vec![1]);
+    }
+
+    #[test]
+    fn test_split_comps_stepwise() {
+        // Two overlapping squares with the same orientation
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(square(1.0, 1.0, 2.0));
+
+        // Each square is split in two points, giving six pieces with two edges each
+        let stages = split_comps_stepwise(curves.clone(), FillRule::NonZero);
+        assert_eq!(stages.built.faces, 4);
+        assert_eq!(stages.built.edges, 24);
+        assert!(stages.wedges_removed.edges <= stages.built.edges);
+        assert!(stages.wedges_removed.fill_numbers.iter().all(|&n| n == 0));
+
+        let mut fill_numbers: Vec<_> = stages.fill_numbers_assigned.fill_numbers.iter().map(|n| n.abs()).collect();
+        fill_numbers.sort();
+        assert_eq!(fill_numbers, vec![0, 1, 1, 2]);
+
+        assert!(stages.faces_simplified.faces <= stages.fill_numbers_assigned.faces);
+        assert_eq!(stages.faces.len(), split_comps(curves, FillRule::NonZero).len());
+    }
+
+    #[test]
+    fn test_face_contours_ref() {
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(square(1.0, 1.0, 2.0));
+
+        let dcel = split_comps_dcel(curves.clone(), FillRule::EvenOdd);
+        let borrowed: Vec<usize> = dcel.face_contours_ref(FillRule::EvenOdd).map(|c| c.count()).collect();
+        let owned: Vec<usize> = split_comps_iter(curves, FillRule::EvenOdd)
+            .flat_map(|f| f.contours.into_iter().map(|c| c.len())).collect();
+        assert_eq!(borrowed, owned);
+
+        let bbox = dcel.face_contours_ref(FillRule::EvenOdd).flatten().map(|c| c.bbox()).fold(None,
+            |acc: Option<Rect>, r| Some(acc.map_or(r, |acc| acc.union(r)))).unwrap();
+        assert!(bbox.corners().0.roughly_equals(Vec2::new(0.0, 0.0)));
+        assert!(bbox.corners().1.roughly_equals(Vec2::new(3.0, 3.0)));
+    }
+
+    #[test]
+    fn test_split_comps_iter() {
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(square(1.0, 1.0, 2.0));
+        curves.extend(square(5.0, 0.0, 1.0));
+
+        // The streamed faces are the ones the DCEL collects, in the same order, and sort like split_comps
+        let same = |f1: &FillFace, f2: &FillFace| f1.fill_number == f2.fill_number && f1.contours == f2.contours;
+        let mut streamed: Vec<_> = split_comps_iter(curves.clone(), FillRule::NonZero).collect();
+        let collected = split_comps_dcel(curves.clone(), FillRule::NonZero).visible_faces(FillRule::NonZero);
+        assert_eq!(streamed.len(), collected.len());
+        assert!(streamed.iter().zip(&collected).all(|(f1, f2)| same(f1, f2)));
+
+        sort_faces(&mut streamed);
+        let faces = split_comps(curves.clone(), FillRule::NonZero);
+        assert_eq!(streamed.len(), faces.len());
+        assert!(streamed.iter().zip(&faces).all(|(f1, f2)| same(f1, f2)));
+
+        // The iterator can stop at the first face
+        let first = split_comps_iter(curves, FillRule::NonZero).next().unwrap();
+        assert!(same(&first, &collected[0]));
+    }
+
+    #[test]
+    fn test_split_comps_including_outer() {
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(square(1.0, 1.0, 2.0));
+
+        let (faces, outer) = split_comps_including_outer(curves.clone(), FillRule::EvenOdd);
+        assert_eq!(faces.len(), split_comps(curves, FillRule::EvenOdd).len());
+        assert!(!outer.is_visible(FillRule::EvenOdd));
+
+        // The outer boundary is the outline of the union, running clockwise
+        assert_eq!(outer.contours.len(), 1);
+        assert_eq!(outer.contours[0].len(), 8);
+        assert!(total_winding(&outer.contours[0]).roughly_equals(-14.0));
+    }
+
+    #[test]
+    fn test_split_comps_per_comp() {
+        let pentagram: Vec<_> = {
+            let pts: Vec<_> = (0..5).map(|i| {
+                let angle = (90.0 + 144.0 * i as Coord).to_radians();
+                Vec2::new(10.0 + 3.0 * angle.cos(), 3.0 * angle.sin())
+            }).collect();
+            (0..5).map(|i| Curve::line(pts[i], pts[(i+1) % 5])).collect()
+        };
+
+        // Each component alone fills the overlap, even if the even-odd rule would empty it globally
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(square(1.0, 1.0, 2.0));
+        assert_eq!(split_comps_per_comp(curves.clone(), &[FillRule::EvenOdd, FillRule::EvenOdd]).len(), 3);
+        assert_eq!(split_comps(curves, FillRule::EvenOdd).len(), 2);
+
+        // The rule of the pentagram decides whether its center is empty, regardless of the square's
+        let mut curves = square(0.0, 0.0, 2.0);
+        curves.extend(pentagram);
+        let faces = split_comps_per_comp(curves.clone(), &[FillRule::NonZero, FillRule::EvenOdd]);
+        assert_eq!(faces.len(), 1 + 5);
+        let faces = split_comps_per_comp(curves.clone(), &[FillRule::EvenOdd, FillRule::NonZero]);
+        assert_eq!(faces.len(), 1 + 6);
+        assert_eq!(split_comps_per_comp(curves.clone(), &[]).len(), split_comps(curves, FillRule::NonZero).len());
+    }
 }
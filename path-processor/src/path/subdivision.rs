@@ -7,43 +7,88 @@
 
 use crate::geometry::*;
 use crate::curve::*;
-use super::FillFace;
+use super::{FillFace, CurveSubdiv};
+
+// The numeric thresholds that control the double curve promotion
+#[derive(Copy, Clone, Debug)]
+pub struct FusionParams {
+    // The dot product of the tangents on the common endpoint must be below this
+    pub max_tangent_dot: Coord,
+    // Fusable curves whose windings differ by more than this ratio are subdivided
+    pub max_winding_ratio: Coord
+}
+
+impl Default for FusionParams {
+    fn default() -> Self {
+        FusionParams { max_tangent_dot: -0.99, max_winding_ratio: 32.0 }
+    }
+}
 
 // Check if two curves are eligible for double curve promotion
-pub fn are_curves_fusable(c1: &Curve, c2: &Curve) -> bool {
+pub fn are_curves_fusable(c1: &Curve, c2: &Curve, params: &FusionParams) -> bool {
     // Bail out if one of them is a line
     if c1.is_line() || c2.is_line() { return false; }
 
-    fn eligible(c1: &Curve, c2: &Curve) -> bool {
+    let eligible = |c1: &Curve, c2: &Curve| {
         // 1) The curves must have a common endpoint
         if !c1.at(1.0).roughly_equals(c2.at(0.0)) { false }
         // 2) The tangents on that endpoint must be similar
-        else if c1.exit_tangent().dot(c2.entry_tangent()) >= -0.99 { false }
+        else if c1.exit_tangent().dot(c2.entry_tangent()) >= params.max_tangent_dot { false }
         // 3) Both must not have the same convexity
         else if c1.is_convex() == c2.is_convex() { false }
         else { true }
-    }
+    };
 
     // If any combination is eligible, return true
     eligible(c1, c2) || eligible(c2, c1)
 }
 
+// Split the cubics of the face according to subdiv, the pieces having equal arc lengths
+pub fn subdivide_by_arc_length(mut face: FillFace, subdiv: CurveSubdiv) -> FillFace {
+    let pieces = match subdiv {
+        CurveSubdiv::ByArcLength(pieces) if pieces > 1 => pieces,
+        _ => return face
+    };
+
+    for contour in &mut face.contours {
+        let mut curves = Vec::with_capacity(contour.len());
+        for curve in contour.drain(..) {
+            if let Curve::CubicBezier(_) = curve {
+                let table = LengthTable::new(&curve, LENGTH_TABLE_SAMPLES);
+                let params: Vec<_> = (0..=pieces)
+                    .map(|i| table.param_at_length(table.length() * i as Coord / pieces as Coord)).collect();
+                curves.extend(params.windows(2).map(|w| curve.subcurve(w[0], w[1])));
+            } else { curves.push(curve); }
+        }
+        *contour = curves;
+    }
+
+    face
+}
+
 fn avg(a: usize, b: usize) -> usize { a/2 + b/2 + (a&b&1) }
 
-pub fn subdivide_overlapping(face: FillFace) -> FillFace {
+// Subdivide the face doing at most max_subdivisions subdivisions, returning the face and the
+// number of subdivisions done; if the limit is hit, the best decomposition so far is returned
+pub fn subdivide_overlapping(face: FillFace, params: &FusionParams, max_subdivisions: usize) -> (FillFace, usize) {
     // An empty face subdivided is an empty face
-    if face.contours.is_empty() { return face; }
+    if face.contours.is_empty() { return (face, 0); }
 
     // Get the vector to store the curves
     let len = face.contours.len();
+    let fill_number = face.fill_number;
     let curves_it = face.contours.into_iter().enumerate();
     let mut curves: Vec<_> = curves_it.flat_map(|(j, contour)| {
         let radix = contour.len().leading_zeros();
         contour.into_iter().enumerate().map(move |(i, c)| (j, i << radix, (i+1) << radix, c))
     }).collect();
 
-    // Utility function for curve subdivision
-    fn subdivide_curve_in(t: Coord, curves: &mut Vec<(usize, usize, usize, Curve)>, node: usize) {
+    // Utility function for curve subdivision, which does nothing once the budget runs out
+    fn subdivide_curve_in(t: Coord, curves: &mut Vec<(usize, usize, usize, Curve)>, node: usize, budget: &mut usize) {
+        if *budget == 0 { return; }
+        *budget -= 1;
+
+        #[cfg(feature = "debug_subdivision")]
         println!("Subdivision happening!");
         let (j, begin, end, _) = curves[node];
         let mid = avg(begin, end);
@@ -53,12 +98,16 @@ pub fn subdivide_overlapping(face: FillFace) -> FillFace {
         curves.push((j, mid, end, curve.subcurve(t, 1.0)));
     }
 
+    let mut budget = max_subdivisions;
     let mut old_len = 0;
     // Iterate for each convex-concave pair until no subdivisions are needed anymore
     while old_len != curves.len() {
         old_len = curves.len();
 
         for n1 in 0..old_len {
+            // No point in checking the other pairs if we can't subdivide anymore
+            if budget == 0 { break; }
+
             // Skip degenerate curves
             if is_curve_degenerate(&curves[n1].3) { continue; }
 
@@ -73,13 +122,13 @@ pub fn subdivide_overlapping(face: FillFace) -> FillFace {
                 // k2: Curve 2 intersects polygon 1
                 if let Some((l1,l2,k1,k2)) = get_intersection_info_from_curves(&curves[n1].3, &curves[n2].3) {
                     if (!l1 && !l2 && !k1 && !k2) || (l1 && l2) || (k1 && k2) {
-                        subdivide_curve_in(0.5, &mut curves, n1);
-                        subdivide_curve_in(0.5, &mut curves, n2);
+                        subdivide_curve_in(0.5, &mut curves, n1, &mut budget);
+                        subdivide_curve_in(0.5, &mut curves, n2, &mut budget);
                     }
-                    else if l1 && !k1 { subdivide_curve_in(0.5, &mut curves, n1); }
-                    else if l2 && !k2 { subdivide_curve_in(0.5, &mut curves, n2); }
-                    else if k1 { subdivide_curve_in(0.5, &mut curves, n2);}
-                    else if k2 { subdivide_curve_in(0.5, &mut curves, n1); }
+                    else if l1 && !k1 { subdivide_curve_in(0.5, &mut curves, n1, &mut budget); }
+                    else if l2 && !k2 { subdivide_curve_in(0.5, &mut curves, n2, &mut budget); }
+                    else if k1 { subdivide_curve_in(0.5, &mut curves, n2, &mut budget);}
+                    else if k2 { subdivide_curve_in(0.5, &mut curves, n1, &mut budget); }
                     else { unreachable!(); }
                 }
             }
@@ -105,8 +154,8 @@ pub fn subdivide_overlapping(face: FillFace) -> FillFace {
             let ikk = if i+2 < curves.len() && curves[i+2].0 == j { i+2 } else { old_i+1 };
 
             // If the triple is an eligible triple for fusion, subdivide the middle curve
-            if are_curves_fusable(&curves[i].3, &curves[ik].3) && are_curves_fusable(&curves[ik].3, &curves[ikk].3) {
-                subdivide_curve_in(0.5, &mut curves, ik);
+            if budget > 0 && are_curves_fusable(&curves[i].3, &curves[ik].3, params) && are_curves_fusable(&curves[ik].3, &curves[ikk].3, params) {
+                subdivide_curve_in(0.5, &mut curves, ik, &mut budget);
                 // Make sure the *second* half of the curve is there
                 let len1 = curves.len()-1;
                 curves.swap(ik, len1);
@@ -134,7 +183,7 @@ pub fn subdivide_overlapping(face: FillFace) -> FillFace {
             let ik = if i+1 < curves.len() && curves[i+1].0 == j { i+1 } else { old_i };
 
             // Only do this for fusable curves
-            if are_curves_fusable(&curves[i].3, &curves[ik].3) {
+            if are_curves_fusable(&curves[i].3, &curves[ik].3, params) {
                 // Get the windings
                 let mut winding1;
                 let mut winding2 = curves[ik].3.winding_at_midpoint().abs();
@@ -146,24 +195,24 @@ pub fn subdivide_overlapping(face: FillFace) -> FillFace {
                 loop {
                     t /= 2.0;
                     winding1 = curves[i].3.subcurve(1.0 - t, 1.0).winding_at_midpoint().abs();
-                    if winding1 <= 32.0 * winding2 { break; }
+                    if winding1 <= params.max_winding_ratio * winding2 { break; }
                 }
 
                 // Bail out if the curve was subdivided
                 if t < 1.0 {
-                    subdivide_curve_in(1.0 - t, &mut curves, i);
+                    subdivide_curve_in(1.0 - t, &mut curves, i, &mut budget);
                 } else {
                     // Otherwise, try to subdivide the other curve
                     t = 2.0;
                     loop {
                         t /= 2.0;
                         winding2 = curves[ik].3.subcurve(0.0, t).winding_at_midpoint().abs();
-                        if winding2 <= 32.0 * winding1 { break; }
+                        if winding2 <= params.max_winding_ratio * winding1 { break; }
                     }
 
                     // Subdivide the curve if necessary
                     if t < 1.0 {
-                        subdivide_curve_in(t, &mut curves, ik);
+                        subdivide_curve_in(t, &mut curves, ik, &mut budget);
                     }
                 }
             }
@@ -179,7 +228,7 @@ pub fn subdivide_overlapping(face: FillFace) -> FillFace {
     for (j,_,_,c) in curves.into_iter() {
         contours[j].push(c);
     }
-    FillFace { contours }
+    (FillFace { contours, fill_number }, max_subdivisions - budget)
 }
 
 fn strictly_inside_convex_polygon(poly: &[Vec2], pt: Vec2) -> bool {
@@ -219,3 +268,43 @@ fn get_intersection_info_from_curves(c1: &Curve, c2: &Curve) -> Option<(bool, bo
         Some((l1,l2,k1,k2))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_subdivision_limit() {
+        // A lens whose second curve's enclosing triangle lies inside the first one's
+        let a = Vec2::new(0.0, 0.0);
+        let b = Vec2::new(2.0, 0.0);
+        let face = || FillFace { contours: vec![vec![
+            Curve::quadratic_bezier(a, Vec2::new(1.0, 2.0), b),
+            Curve::quadratic_bezier(b, Vec2::new(1.0, 0.5), a)
+        ]], fill_number: 1 };
+
+        let (full, count) = subdivide_overlapping(face(), &FusionParams::default(), usize::MAX);
+        assert!(count > 0);
+        assert_eq!(full.contours[0].len(), 2 + count);
+
+        let (limited, count) = subdivide_overlapping(face(), &FusionParams::default(), 1);
+        assert_eq!(count, 1);
+        assert_eq!(limited.contours[0].len(), 3);
+    }
+
+    #[test]
+    fn test_tangent_threshold() {
+        // Two curves of opposite convexity meeting at the origin at a sharp angle
+        let c1 = Curve::quadratic_bezier(Vec2::new(-2.0, 0.0), Vec2::new(-1.0, 2.0), Vec2::new(0.0, 0.0));
+        let c2 = Curve::quadratic_bezier(Vec2::new(0.0, 0.0), Vec2::new(-1.0, 1.5), Vec2::new(-2.0, 1.0));
+        assert!(c1.is_convex() != c2.is_convex());
+
+        // They are fusable only if the dot product of their tangents is below the threshold
+        let dot = c1.exit_tangent().dot(c2.entry_tangent());
+        let params = |max_tangent_dot| FusionParams { max_tangent_dot, ..FusionParams::default() };
+        assert!(are_curves_fusable(&c1, &c2, &params(dot + 1e-9)));
+        assert!(!are_curves_fusable(&c1, &c2, &params(dot - 1e-9)));
+        assert!(!are_curves_fusable(&c1, &c2, &params(dot)));
+        assert!(are_curves_fusable(&c2, &c1, &params(dot + 1e-9)));
+    }
+}
\ No newline at end of file
@@ -7,6 +7,7 @@
 
 use crate::geometry::*;
 use crate::derive_more::*;
+use super::tessellation::*;
 
 #[derive(Copy, Clone, Debug, Display)]
 #[display(fmt = "Triangle({},{},{})", a, b, c)]
@@ -15,14 +16,26 @@ pub struct Triangle {
 }
 
 impl Triangle {
-    pub fn new(a: Vec2, mut b: Vec2, mut c: Vec2) -> Triangle {
-        if (b-a).cross(c-a) < 0.0 { std::mem::swap(&mut b, &mut c) }
+    pub fn new(a: Vec2, b: Vec2, c: Vec2) -> Triangle { Triangle::new_oriented(a, b, c, true) }
+
+    // Build the triangle with the given winding (counter-clockwise if ccw, in a y-up space)
+    pub fn new_oriented(a: Vec2, mut b: Vec2, mut c: Vec2, ccw: bool) -> Triangle {
+        if ((b-a).cross(c-a) < 0.0) == ccw { std::mem::swap(&mut b, &mut c) }
         Triangle { a, b, c }
     }
 
     pub fn is_degenerate(&self) -> bool { (self.b-self.a).cross(self.c-self.a).roughly_zero() }
 }
 
+// The Loop-Blinn implicit function of the texture coordinates, which is negative inside the curve's
+// region: cubics (w = 0) use x³ - yz, while quadratics and arcs (w = ±1) use w(x² - yz)
+pub fn implicit_value(tex: Vec4) -> Coord {
+    if tex.w.abs() < 0.5 { tex.x * tex.x * tex.x - tex.y * tex.z }
+    else { tex.w * (tex.x * tex.x - tex.y * tex.z) }
+}
+
+// A vertex of a curve triangle; the texture coordinates are interpolated linearly across the
+// triangle, and the fragment is covered where implicit_value(tex) < 0
 #[derive(Copy, Clone, Debug, Constructor, Display)]
 #[display(fmt = "({},{})", pos, tex)]
 pub struct CurveVertex {
@@ -43,14 +56,27 @@ pub struct CurveTriangle {
 }
 
 impl CurveTriangle {
-    pub fn new(a: CurveVertex, mut b: CurveVertex, mut c: CurveVertex) -> CurveTriangle {
-        if (b.pos-a.pos).cross(c.pos-a.pos) < 0.0 { std::mem::swap(&mut b, &mut c) }
+    pub fn new(a: CurveVertex, b: CurveVertex, c: CurveVertex) -> CurveTriangle {
+        CurveTriangle::new_oriented(a, b, c, true)
+    }
+
+    pub fn new_oriented(a: CurveVertex, mut b: CurveVertex, mut c: CurveVertex, ccw: bool) -> CurveTriangle {
+        if ((b.pos-a.pos).cross(c.pos-a.pos) < 0.0) == ccw { std::mem::swap(&mut b, &mut c) }
         CurveTriangle { a, b, c }
     }
 
     pub fn is_degenerate(&self) -> bool { (self.b.pos-self.a.pos).cross(self.c.pos-self.a.pos).roughly_zero() }
+
+    // Flatten the curve's region inside the triangle into plain triangles
+    pub fn tessellate(&self, tolerance: Coord, out: &mut Vec<Triangle>) {
+        let v = |v: CurveVertex| TessellationVertex { pos: v.pos, tex: [v.tex, Vec4::zero()] };
+        tessellate_region(out, [v(self.a), v(self.b), v(self.c)], 1, false, tolerance);
+    }
 }
 
+// A vertex of a double curve triangle, which carries the texture coordinates of two fused curves,
+// following the same convention as CurveVertex; the fragment is covered where either curve covers it
+// if the regions are a disjoint union, and where both cover it otherwise
 #[derive(Copy, Clone, Debug, Constructor, Display)]
 #[display(fmt = "({},{},{})", pos, tex0, tex1)]
 pub struct DoubleCurveVertex {
@@ -58,6 +84,10 @@ pub struct DoubleCurveVertex {
 }
 
 impl DoubleCurveVertex {
+    // Whether the coverages of tex0 and tex1 are joined (min of the implicit values) rather than
+    // intersected (max of the implicit values)
+    pub fn is_disjoint_union(&self) -> bool { self.disjoint_union }
+
     pub fn make_triangle_fan(vertices: &[DoubleCurveVertex]) -> impl Iterator<Item = DoubleCurveTriangle> + '_ {
         let len = vertices.len();
         (2..len).into_iter().map(move |i| DoubleCurveTriangle::new(vertices[0], vertices[i-1], vertices[i]))
@@ -71,10 +101,21 @@ pub struct DoubleCurveTriangle {
 }
 
 impl DoubleCurveTriangle {
-    pub fn new(a: DoubleCurveVertex, mut b: DoubleCurveVertex, mut c: DoubleCurveVertex) -> DoubleCurveTriangle {
-        if (b.pos-a.pos).cross(c.pos-a.pos) < 0.0 { std::mem::swap(&mut b, &mut c) }
+    pub fn new(a: DoubleCurveVertex, b: DoubleCurveVertex, c: DoubleCurveVertex) -> DoubleCurveTriangle {
+        DoubleCurveTriangle::new_oriented(a, b, c, true)
+    }
+
+    pub fn new_oriented(a: DoubleCurveVertex, mut b: DoubleCurveVertex, mut c: DoubleCurveVertex, ccw: bool)
+        -> DoubleCurveTriangle {
+        if ((b.pos-a.pos).cross(c.pos-a.pos) < 0.0) == ccw { std::mem::swap(&mut b, &mut c) }
         DoubleCurveTriangle { a, b, c }
     }
 
     pub fn is_degenerate(&self) -> bool { (self.b.pos-self.a.pos).cross(self.c.pos-self.a.pos).roughly_zero() }
+
+    // Flatten the region of both curves inside the triangle into plain triangles
+    pub fn tessellate(&self, tolerance: Coord, out: &mut Vec<Triangle>) {
+        let v = |v: DoubleCurveVertex| TessellationVertex { pos: v.pos, tex: [v.tex0, v.tex1] };
+        tessellate_region(out, [v(self.a), v(self.b), v(self.c)], 2, self.a.is_disjoint_union(), tolerance);
+    }
 }
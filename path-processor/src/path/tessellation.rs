@@ -0,0 +1,113 @@
+//------------------------------------------------------------------------------
+// tessellation.rs
+//------------------------------------------------------------------------------
+// Provides the flattening of the curve triangles into plain triangles, for the
+// renderers that can't evaluate the Loop-Blinn implicit functions
+//------------------------------------------------------------------------------
+
+use crate::geometry::*;
+use crate::arrayvec::ArrayVec;
+use super::subdivision_structs::*;
+
+// The maximum number of times a triangle is split, guarding against tiny tolerances
+const MAX_TESSELLATION_DEPTH: usize = 24;
+
+// A vertex of a triangle being split, carrying the texture coordinates of up to two curves
+#[derive(Clone, Copy)]
+pub struct TessellationVertex { pub pos: Vec2, pub tex: [Vec4; 2] }
+
+impl TessellationVertex {
+    fn midpoint(&self, other: &TessellationVertex) -> TessellationVertex {
+        let tex = [(self.tex[0] + other.tex[0]) / 2.0, (self.tex[1] + other.tex[1]) / 2.0];
+        TessellationVertex { pos: (self.pos + other.pos) / 2.0, tex }
+    }
+}
+
+fn is_cubic(tex: Vec4) -> bool { tex.w.abs() < 0.5 }
+
+// The symmetric multilinear forms that correspond to the implicit functions, written in homogeneous
+// form (using that the barycentric coordinates sum to 1), so their values on the triangle's vertices
+// give the Bernstein coefficients of the implicit function over the triangle
+fn conic_form(u: Vec4, v: Vec4) -> Coord {
+    u.w * (u.x * v.x - (u.y * v.z + u.z * v.y) / 2.0)
+}
+
+fn cubic_form(u: Vec4, v: Vec4, w: Vec4) -> Coord {
+    let lm = |u: Vec4, v: Vec4| (u.y * v.z + u.z * v.y) / 2.0;
+    u.x * v.x * w.x - (lm(u, v) + lm(v, w) + lm(u, w)) / 3.0
+}
+
+// Whether the curve's region covers the whole triangle (Some(true)), none of it (Some(false)) or only
+// a part of it (None); since a Bézier polynomial lies in the convex hull of its coefficients, a triangle
+// whose coefficients all have the same sign can't have a piece of the curve inside it
+fn classify(t: [Vec4; 3]) -> Option<bool> {
+    let mut coefs = ArrayVec::<[Coord; 10]>::new();
+    if is_cubic(t[0]) {
+        for i in 0..3 {
+            for j in i..3 {
+                for k in j..3 { coefs.push(cubic_form(t[i], t[j], t[k])); }
+            }
+        }
+    } else {
+        for i in 0..3 {
+            for j in i..3 { coefs.push(conic_form(t[i], t[j])); }
+        }
+    }
+
+    if coefs.iter().all(|&c| c <= 0.0) { Some(true) }
+    else if coefs.iter().all(|&c| c >= 0.0) { Some(false) }
+    else { None }
+}
+
+// Combine the states of the curves: the double curves either join or intersect their regions
+fn combine(states: impl Iterator<Item = Option<bool>>, disjoint_union: bool) -> Option<bool> {
+    let (mut all_true, mut all_false, mut any_true, mut any_false) = (true, true, false, false);
+    for s in states {
+        all_true &= s == Some(true);
+        all_false &= s == Some(false);
+        any_true |= s == Some(true);
+        any_false |= s == Some(false);
+    }
+
+    if disjoint_union {
+        if any_true { Some(true) } else if all_false { Some(false) } else { None }
+    } else if all_true { Some(true) } else if any_false { Some(false) } else { None }
+}
+
+// Split the triangle until each piece is either completely inside or outside the region, or
+// smaller than the tolerance (in which case its centroid decides whether it is inside)
+pub fn tessellate_region(out: &mut Vec<Triangle>, v: [TessellationVertex; 3], curves: usize,
+    disjoint_union: bool, tolerance: Coord) {
+    tessellate_region_rec(out, v, curves, disjoint_union, tolerance, 0);
+}
+
+fn tessellate_region_rec(out: &mut Vec<Triangle>, v: [TessellationVertex; 3], curves: usize,
+    disjoint_union: bool, tolerance: Coord, depth: usize) {
+    let states = (0..curves).map(|k| classify([v[0].tex[k], v[1].tex[k], v[2].tex[k]]));
+
+    match combine(states, disjoint_union) {
+        Some(true) => out.push(Triangle::new(v[0].pos, v[1].pos, v[2].pos)),
+        Some(false) => {},
+        None => {
+            let longest = (v[1].pos - v[0].pos).length().max((v[2].pos - v[1].pos).length())
+                .max((v[0].pos - v[2].pos).length());
+
+            if longest <= tolerance || depth >= MAX_TESSELLATION_DEPTH {
+                let centroid = [(v[0].tex[0] + v[1].tex[0] + v[2].tex[0]) / 3.0,
+                    (v[0].tex[1] + v[1].tex[1] + v[2].tex[1]) / 3.0];
+                let states = (0..curves).map(|k| Some(implicit_value(centroid[k]) < 0.0));
+                if combine(states, disjoint_union) == Some(true) {
+                    out.push(Triangle::new(v[0].pos, v[1].pos, v[2].pos));
+                }
+            } else {
+                let m01 = v[0].midpoint(&v[1]);
+                let m12 = v[1].midpoint(&v[2]);
+                let m20 = v[2].midpoint(&v[0]);
+
+                for tri in [[v[0], m01, m20], [m01, v[1], m12], [m20, m12, v[2]], [m01, m12, m20]].iter() {
+                    tessellate_region_rec(out, *tri, curves, disjoint_union, tolerance, depth + 1);
+                }
+            }
+        }
+    }
+}
@@ -13,11 +13,13 @@ use super::subdivision_structs::*;
 use std::ops::Bound::*;
 use vertex::*;
 use edge::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::cmp::Ordering;
 use crate::merge::*;
+use crate::ordered_float::OrderedFloat;
 
-#[allow(dead_code)]
+// Printing function
+#[cfg(feature = "debug_subdivision")]
 fn print_vec<T: std::fmt::Display>(v: &[T]) {
     if v.is_empty() { println!("[]"); }
     else {
@@ -29,21 +31,167 @@ fn print_vec<T: std::fmt::Display>(v: &[T]) {
     }
 }
 
+#[cfg(not(feature = "debug_subdivision"))]
+fn print_vec<T: std::fmt::Display>(_v: &[T]) {}
+
 // The algorithm used here is explained on Chapter 3 on
 // "Computational Geometry: Algorithms and Applications", de Berg et al
+// The contours can come in either orientation, since they are oriented by their nesting first
 pub fn triangulate(contours: impl Iterator<Item = Vec<Vec2>>) -> Vec<Triangle> {
-    // Firstly, simplify the contours
-    let contours = contours.map(|c| simplify_polygon(&c));
+    triangulate_iter(contours).collect()
+}
+
+// Same as triangulate, but yielding the triangles as each y-monotone piece is triangulated,
+// so only the triangles of one piece are held at a time
+pub fn triangulate_iter(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iterator<Item = Triangle> {
+    triangulate_presimplified_iter(simplify_contours(contours))
+}
+
+// Simplify the contours, dropping the ones which have no area at all
+fn simplify_contours(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iterator<Item = Vec<Vec2>> {
+    contours.map(|c| simplify_polygon(&c))
+        .filter(|c| c.len() >= 3 && !polygon_winding(c).roughly_zero_squared())
+}
+
+// Same as triangulate, but telling for each triangle which of its edges lie on the contours, as
+// opposed to the diagonals added by the triangulation; the edge i goes from the vertex i to the
+// vertex (i+1) % 3, and only the flagged ones are part of the silhouette, to be anti-aliased
+pub fn triangulate_with_edge_flags(contours: impl Iterator<Item = Vec<Vec2>>) -> Vec<(Triangle, [bool; 3])> {
+    triangulate_oriented_iter(orient_contours(simplify_contours(contours).collect()).into_iter()).collect()
+}
+
+// Triangulate contours that are already simple, skipping their simplification, so every vertex
+// is kept as given; the contours must not have repeated or collinear consecutive vertices
+pub fn triangulate_presimplified(contours: impl Iterator<Item = Vec<Vec2>>) -> Vec<Triangle> {
+    triangulate_presimplified_iter(contours).collect()
+}
+
+fn triangulate_presimplified_iter(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iterator<Item = Triangle> {
+    triangulate_oriented_iter(orient_contours(contours.collect()).into_iter()).map(|(t, _)| t)
+}
+
+// Same as triangulate, but for contours that are already oriented by their nesting, like the
+// faces' contours out of split_comps, skipping the nesting tests between every pair of contours
+pub fn triangulate_oriented(contours: impl Iterator<Item = Vec<Vec2>>) -> Vec<Triangle> {
+    triangulate_oriented_iter(simplify_contours(contours)).map(|(t, _)| t).collect()
+}
+
+fn triangulate_oriented_iter(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iterator<Item = (Triangle, [bool; 3])> {
+    // Partition the polygon into y-monotone pieces and triangulate them
+    partition_to_monotone(contours).flat_map(|(polygon, on_contour)| {
+        let mut triangles = Vec::with_capacity(polygon.len().saturating_sub(2));
+        triangulate_monotone(&mut triangles, &polygon);
+
+        // Check that the triangles cover the polygon exactly, or the stack logic went astray on some
+        // degenerate configuration; in that case fall back to ear clipping, which is slower but still
+        // fills the polygon
+        if !covers_polygon(&triangles, &polygon) {
+            debug_assert!(false, "the monotone triangulation doesn't cover the polygon");
+            triangles.clear();
+            triangulate_ear_clipping(&mut triangles, &polygon);
+        }
+
+        // The edges between consecutive vertices of the piece are its sides, which lie on the contours
+        // unless the partition added them; any other edge is a diagonal of the piece
+        let len = polygon.len();
+        let on_contour = move |i: usize, j: usize| {
+            if (i+1) % len == j { on_contour[i] } else if (j+1) % len == i { on_contour[j] } else { false }
+        };
+        triangles.into_iter().map(move |[i, mut j, mut k]| {
+            if (polygon[j] - polygon[i]).cross(polygon[k] - polygon[i]) < 0.0 { std::mem::swap(&mut j, &mut k); }
+            (Triangle { a: polygon[i], b: polygon[j], c: polygon[k] }, [on_contour(i, j), on_contour(j, k), on_contour(k, i)])
+        })
+    })
+    // Remove the degenerate triangles
+    .filter(|(t, _)| !t.is_degenerate())
+}
+
+// The sweep classifies the vertices assuming the interior is on the left of the contours, so
+// orient them by their nesting: the ones inside an even number of others are outer boundaries,
+// made counterclockwise, and the other ones are holes, made clockwise
+fn orient_contours(mut contours: Vec<Vec<Vec2>>) -> Vec<Vec<Vec2>> {
+    // A lone contour can't be nested
+    if let [contour] = contours.as_mut_slice() {
+        if polygon_winding(contour) < 0.0 { contour.reverse(); }
+        return contours;
+    }
+
+    let bounds: Vec<_> = contours.iter().map(|c| Rect::enclosing_rect(c.iter().copied())).collect();
+    let holes: Vec<_> = (0..contours.len()).map(|i| {
+        let containers = (0..contours.len())
+            .filter(|&j| j != i && contains_contour(&contours[j], bounds[j], &contours[i]));
+        containers.count() % 2 != 0
+    }).collect();
+
+    for (contour, hole) in contours.iter_mut().zip(holes) {
+        if (polygon_winding(contour) > 0.0) == hole { contour.reverse(); }
+    }
+    contours
+}
 
-    // Then partition the polygon into y-monotone pieces and triangulate them
-    let mut triangles = Vec::new();
-    for polygon in partition_to_monotone(contours) {
-        triangulate_monotone(&mut triangles, polygon);
+// Whether the inner contour is inside the outer one; the contours don't cross, but they can touch,
+// so the first vertex or edge midpoint of the inner contour that isn't on the outer one decides
+fn contains_contour(outer: &[Vec2], bounds: Option<Rect>, inner: &[Vec2]) -> bool {
+    if inner.is_empty() || !bounds.is_some_and(|b| b.contains_point(inner[0])) { return false; }
+
+    let len = inner.len();
+    let points = (0..len)
+        .flat_map(|i| std::iter::once(inner[i]).chain(std::iter::once((inner[i] + inner[(i+1) % len]) / 2.0)));
+    for p in points {
+        let (strict, loose) = (polygon_contains_point(outer, p, true), polygon_contains_point(outer, p, false));
+        if strict == loose { return strict; }
     }
+    false
+}
+
+// Triangulate the contours, outputting an indexed triangle list instead
+// The vertices all come from the contours themselves, so exact equality is enough to deduplicate them
+pub fn triangulate_indexed(contours: impl Iterator<Item = Vec<Vec2>>) -> (Vec<Vec2>, Vec<[u32; 3]>) {
+    let triangles = triangulate(contours);
 
-    // Remove degenerate triangles and return
-    triangles.retain(|t| !t.is_degenerate());
-    triangles
+    let mut vertices = Vec::new();
+    let mut vertex_indices = HashMap::new();
+    let mut indices = Vec::with_capacity(triangles.len());
+
+    for t in triangles {
+        let mut tri = [0; 3];
+        for (i, v) in [t.a, t.b, t.c].iter().enumerate() {
+            tri[i] = *vertex_indices.entry((OrderedFloat(v.x), OrderedFloat(v.y))).or_insert_with(|| {
+                vertices.push(*v);
+                (vertices.len() - 1) as u32
+            });
+        }
+        indices.push(tri);
+    }
+
+    (vertices, indices)
+}
+
+// Triangulate a polygon with holes, normalizing the orientation of the contours beforehand
+pub fn triangulate_with_holes(outer: &[Vec2], holes: &[Vec<Vec2>]) -> Vec<Triangle> {
+    fn oriented(poly: &[Vec2], ccw: bool) -> Vec<Vec2> {
+        let mut poly = poly.to_vec();
+        if (polygon_winding(&poly) > 0.0) != ccw { poly.reverse(); }
+        poly
+    }
+
+    let contours = std::iter::once(oriented(outer, true))
+        .chain(holes.iter().map(|h| oriented(h, false)));
+    triangulate(contours)
+}
+
+// Find the outgoing edge of the vertex whose face contains the given direction; the face to the
+// left of an outgoing edge spans counterclockwise from it to the edge that comes before it
+fn search_sector(vertex: &DcelVertex, edges: &[Edge], dir: Vec2) -> usize {
+    let ccw_angle = |from: Vec2, to: Vec2| {
+        let angle = from.angle_between(to);
+        if angle <= 0.0 { angle + TWO_PI } else { angle }
+    };
+
+    *vertex.outgoing.iter().find(|&&e| {
+        let (out, back) = (edges[e].key.b - vertex.cur, edges[edges[e].prev].key.a - vertex.cur);
+        ccw_angle(out, dir) < ccw_angle(out, back)
+    }).expect("the diagonal must lie inside the polygon")
 }
 
 // Utility to split the diagonal
@@ -53,11 +201,11 @@ fn split_diagonal(vertices: &mut Vec<DcelVertex>, edges: &mut Vec<Edge>, v1: usi
     edges.push(Edge::new(vertices[v1].cur, vertices[v2].cur));
     edges.push(Edge::new(vertices[v2].cur, vertices[v1].cur));
 
-    let (e1lo, _) = vertices[v1].search_outgoing(edges[e12].key).unwrap();
-    let (_, e1ri) = vertices[v1].search_incoming(edges[e21].key).unwrap();
+    let e1lo = search_sector(&vertices[v1], edges, vertices[v2].cur - vertices[v1].cur);
+    let e1ri = edges[e1lo].prev;
 
-    let (e2lo, _) = vertices[v2].search_outgoing(edges[e21].key).unwrap();
-    let (_, e2ri) = vertices[v2].search_incoming(edges[e12].key).unwrap();
+    let e2lo = search_sector(&vertices[v2], edges, vertices[v1].cur - vertices[v2].cur);
+    let e2ri = edges[e2lo].prev;
 
     edges[e1ri].next = e12;
     edges[e2lo].prev = e12;
@@ -71,11 +219,8 @@ fn split_diagonal(vertices: &mut Vec<DcelVertex>, edges: &mut Vec<Edge>, v1: usi
     edges[e21].next = e1lo;
     edges[e21].prev = e2ri;
 
-    vertices[v1].outgoing.insert(edges[e12].key, e12);
-    vertices[v1].incoming.insert(edges[e21].key, e21);
-
-    vertices[v2].outgoing.insert(edges[e21].key, e21);
-    vertices[v2].incoming.insert(edges[e12].key, e12);
+    vertices[v1].outgoing.push(e12);
+    vertices[v2].outgoing.push(e21);
 
     check_cycle(edges, e12);
     check_cycle(edges, e21);
@@ -96,13 +241,16 @@ fn check_cycle(edges: &Vec<Edge>, e: usize)
     }
 }
 
-fn search_edge(edges: &BTreeMap<EdgeKey, usize>, v: Vec2) -> usize {
-    *edges.range((Unbounded, Excluded(EdgeKey { a: v, b: v }))).next_back().unwrap().1
+// Find the edge directly to the left of v, which may not exist if the polygon isn't simple
+fn search_edge(edges: &BTreeMap<EdgeKey, usize>, v: Vec2) -> Option<usize> {
+    edges.range((Unbounded, Excluded(EdgeKey { a: v, b: v }))).next_back().map(|(_, &e)| e)
 }
 
 // FIXME: There is still an error on this implementation, that makes it fail for bigpath.txt, even though
 // its counterpart works correctly in the C# project
-fn partition_to_monotone(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iterator<Item = Vec<Vec2>> {
+// Each piece comes with whether each of its edges, from the vertex i to the next one, lies on the
+// contours, as opposed to being one of the diagonals added here
+fn partition_to_monotone(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iterator<Item = (Vec<Vec2>, Vec<bool>)> {
     // Sort all vertices using their default comparison
     let mut vertices = Vec::new();
     let mut edges = Vec::new();
@@ -113,25 +261,27 @@ fn partition_to_monotone(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iter
         if poly.len() < 3 || segment_equivalent(&poly).is_some() { continue; }
         print_vec(&poly);
 
+        // The vertices and edges of all contours share the same arrays
+        let base = vertices.len();
+
         // To make the circular list
         for i in 0..poly.len() {
             let prev = poly[if i == 0 { poly.len() } else { i } - 1];
             let cur = poly[i];
             let next = poly[if i == poly.len()-1 { 0 } else { i+1 }];
 
-            let v = i;
+            let v = base + i;
             vertices.push(DcelVertex::new(prev, cur, next));
 
             // Build the circular list
-            let e = i;
+            let e = base + i;
             edges.push(Edge::new(cur, next));
             vertices[v].next_edge = e;
-            vertices[v].outgoing.insert(edges[e].key, e);
+            vertices[v].outgoing.push(e);
 
-            if v > 0 {
+            if v > base {
                 let prev = vertices[v-1].next_edge;
                 vertices[v].prev_edge = prev;
-                vertices[v].incoming.insert(edges[prev].key, prev);
                 edges[vertices[v].next_edge].prev = vertices[v].prev_edge;
                 edges[vertices[v].prev_edge].next = vertices[v].next_edge;
             }
@@ -140,12 +290,14 @@ fn partition_to_monotone(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iter
         // Close the loop
         let len = vertices.len();
         let prev = vertices[len-1].next_edge;
-        vertices[0].prev_edge = prev;
-        vertices[0].incoming.insert(edges[prev].key, prev);
-        edges[vertices[0].next_edge].prev = vertices[0].prev_edge;
-        edges[vertices[0].prev_edge].next = vertices[0].next_edge;
+        vertices[base].prev_edge = prev;
+        edges[vertices[base].next_edge].prev = vertices[base].prev_edge;
+        edges[vertices[base].prev_edge].next = vertices[base].next_edge;
     }
 
+    // The diagonals are added after the edges of the contours
+    let contour_edges = edges.len();
+
     // Put all the vertices (indices) into an array, and swipe from up to down
     let mut vinds: Vec<_> = (0..vertices.len()).collect();
     vinds.sort_unstable_by(|&i,&j| vertices[i].cmp(&vertices[j]));
@@ -158,11 +310,13 @@ fn partition_to_monotone(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iter
     for i in vinds {
         match vertices[i].type_ {
             VertexType::Start | VertexType::Split => {
+                // If there's no edge to the left, the diagonal is skipped
                 if vertices[i].type_ == VertexType::Split {
-                    let eleft = search_edge(&edges_tmp, vertices[i].cur);
-                    let helper = edges[eleft].helper_vertex;
-                    split_diagonal(&mut vertices, &mut edges, i, helper);
-                    edges[eleft].helper_vertex = i;
+                    if let Some(eleft) = search_edge(&edges_tmp, vertices[i].cur) {
+                        let helper = edges[eleft].helper_vertex;
+                        split_diagonal(&mut vertices, &mut edges, i, helper);
+                        edges[eleft].helper_vertex = i;
+                    }
                 }
 
                 let e = vertices[i].next_edge;
@@ -178,12 +332,13 @@ fn partition_to_monotone(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iter
                 edges_tmp.remove(&edges[e].key);
 
                 if vertices[i].type_ == VertexType::Merge {
-                    let eleft = search_edge(&edges_tmp, vertices[i].cur);
-                    let helper = edges[eleft].helper_vertex;
-                    if vertices[helper].type_ == VertexType::Merge {
-                        split_diagonal(&mut vertices, &mut edges, i, helper);
+                    if let Some(eleft) = search_edge(&edges_tmp, vertices[i].cur) {
+                        let helper = edges[eleft].helper_vertex;
+                        if vertices[helper].type_ == VertexType::Merge {
+                            split_diagonal(&mut vertices, &mut edges, i, helper);
+                        }
+                        edges[eleft].helper_vertex = i;
                     }
-                    edges[eleft].helper_vertex = i;
                 }
             }
             VertexType::RegularLeft => {
@@ -199,12 +354,13 @@ fn partition_to_monotone(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iter
                 edges_tmp.insert(edges[e].key, e);
             },
             VertexType::RegularRight => {
-                let eleft = search_edge(&edges_tmp, vertices[i].cur);
-                let helper = edges[eleft].helper_vertex;
-                if vertices[helper].type_ == VertexType::Merge {
-                    split_diagonal(&mut vertices, &mut edges, i, helper);
+                if let Some(eleft) = search_edge(&edges_tmp, vertices[i].cur) {
+                    let helper = edges[eleft].helper_vertex;
+                    if vertices[helper].type_ == VertexType::Merge {
+                        split_diagonal(&mut vertices, &mut edges, i, helper);
+                    }
+                    edges[eleft].helper_vertex = i;
                 }
-                edges[eleft].helper_vertex = i;
             }
         }
     }
@@ -217,6 +373,7 @@ fn partition_to_monotone(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iter
         if all_edges[e] { None }
         else {
             let mut pts = vec![edges[e].key.a];
+            let mut on_contour = vec![e < contour_edges];
             all_edges[e] = true;
             let mut c = edges[e].next;
 
@@ -225,10 +382,11 @@ fn partition_to_monotone(contours: impl Iterator<Item = Vec<Vec2>>) -> impl Iter
                     panic!("Cycle detected!");
                 }
                 pts.push(edges[c].key.a);
+                on_contour.push(c < contour_edges);
                 all_edges[c] = true;
                 c = edges[c].next;
             }
-            Some(pts)
+            Some((pts, on_contour))
         }
     })
 }
@@ -251,23 +409,53 @@ fn special_point(poly: &[Vec2], bflag: bool) -> usize {
     0
 }
 
-fn triangulate_monotone(triangles: &mut Vec<Triangle>, polygon: Vec<Vec2>) {
+// Whether the triangles add up to the area of the polygon; an overlapping triangle counts its area
+// in full, so it cannot be compensated by a missing one
+fn covers_polygon(triangles: &[[usize; 3]], polygon: &[Vec2]) -> bool {
+    let area = polygon_winding(polygon).abs();
+    let covered: Coord = triangles.iter()
+        .map(|&[i, j, k]| (polygon[j] - polygon[i]).cross(polygon[k] - polygon[i]).abs()).sum();
+    (covered - area).abs() <= EPSILON * (1.0 + area)
+}
+
+// Clip the convex vertices whose triangle contains no other vertex, one at a time; the polygon must
+// be counterclockwise, and if there is no such vertex, as on degenerate polygons, the first one goes
+fn triangulate_ear_clipping(triangles: &mut Vec<[usize; 3]>, polygon: &[Vec2]) {
+    let mut indices: Vec<_> = (0..polygon.len()).collect();
+    while indices.len() >= 3 {
+        let n = indices.len();
+        let corner = |i: usize| [indices[(i+n-1) % n], indices[i], indices[(i+1) % n]];
+        let ear = (0..n).find(|&i| {
+            let [a, b, c] = corner(i);
+            let (a, b, c) = (polygon[a], polygon[b], polygon[c]);
+            let inside = |p: Vec2| p != a && p != b && p != c
+                && (b - a).cross(p - a) >= 0.0 && (c - b).cross(p - b) >= 0.0 && (a - c).cross(p - c) >= 0.0;
+            (b - a).cross(c - b) > 0.0 && !indices.iter().any(|&j| inside(polygon[j]))
+        }).unwrap_or(0);
+
+        triangles.push(corner(ear));
+        indices.remove(ear);
+    }
+}
+
+// Triangulate the y-monotone polygon, outputting each triangle as the indices of its vertices
+fn triangulate_monotone(triangles: &mut Vec<[usize; 3]>, polygon: &[Vec2]) {
     let len = polygon.len();
 
     // Account for degenerate cases
-    if len == 2 { return; }
+    if len < 3 { return; }
     else if len == 3 {
-        triangles.push(Triangle::new(polygon[0], polygon[1], polygon[2]));
+        triangles.push([0, 1, 2]);
         return;
     }
 
     // Locate the beginning and the end of the chain
-    let begin = special_point(polygon.as_slice(), true);
-    let end = special_point(polygon.as_slice(), false);
+    let begin = special_point(polygon, true);
+    let end = special_point(polygon, false);
 
     // The left and right chains
-    let chain_creator_left = |i| ChainVertex { pos: polygon[i], type_: VertexType::RegularLeft };
-    let chain_creator_right = |i| ChainVertex { pos: polygon[i], type_: VertexType::RegularRight };
+    let chain_creator_left = |i| ChainVertex { pos: polygon[i], index: i, type_: VertexType::RegularLeft };
+    let chain_creator_right = |i| ChainVertex { pos: polygon[i], index: i, type_: VertexType::RegularRight };
 
     let mut vertices: Vec<_> = if begin < end {
         let left_chain = (begin+1..end).map(chain_creator_left).rev();
@@ -281,7 +469,7 @@ fn triangulate_monotone(triangles: &mut Vec<Triangle>, polygon: Vec<Vec2>) {
     vertices.reverse();
 
     // Create the stack
-    let mut stack = vec![ChainVertex { pos: polygon[begin], type_: VertexType::Start }, vertices[0]];
+    let mut stack = vec![ChainVertex { pos: polygon[begin], index: begin, type_: VertexType::Start }, vertices[0]];
 
     // Operate on the vertices
     for j in 1..vertices.len() {
@@ -291,7 +479,7 @@ fn triangulate_monotone(triangles: &mut Vec<Triangle>, polygon: Vec<Vec2>) {
         if vert.type_ != VertexType::Start && pvert.type_ != vert.type_ {
             while !stack.is_empty() {
                 let other = stack.remove(stack.len()-1);
-                triangles.push(Triangle::new(pvert.pos, vert.pos, other.pos));
+                triangles.push([pvert.index, vert.index, other.index]);
                 vert = other;
             }
 
@@ -308,7 +496,7 @@ fn triangulate_monotone(triangles: &mut Vec<Triangle>, polygon: Vec<Vec2>) {
             let mut other = stack[stack.len()-1];
 
             while can_make_diagonal(&other, &vert, &pvert) {
-                triangles.push(Triangle::new(pvert.pos, vert.pos, other.pos));
+                triangles.push([pvert.index, vert.index, other.index]);
                 stack.remove(stack.len()-1);
                 vert = other;
                 if stack.is_empty() { break; }
@@ -323,13 +511,220 @@ fn triangulate_monotone(triangles: &mut Vec<Triangle>, polygon: Vec<Vec2>) {
 
     // Push last vertex
     if !stack.is_empty() {
-        let pvert = polygon[end];
         let mut vert = stack.remove(stack.len()-1);
 
         while !stack.is_empty() {
             let other = stack.remove(stack.len()-1);
-            triangles.push(Triangle::new(pvert, vert.pos, other.pos));
+            triangles.push([end, vert.index, other.index]);
             vert = other;
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_collinear_contour() {
+        let contour: Vec<_> = (0..5).map(|i| Vec2::new(i as Coord, 2.0 * i as Coord)).collect();
+        assert!(triangulate(std::iter::once(contour)).is_empty());
+    }
+
+    #[test]
+    fn test_repeated_point_contour() {
+        let contour = vec![Vec2::new(1.0, 1.0); 4];
+        assert!(triangulate(std::iter::once(contour)).is_empty());
+        assert!(triangulate(std::iter::once(vec![Vec2::new(1.0, 1.0)])).is_empty());
+    }
+
+    #[test]
+    fn test_missing_left_edge() {
+        // A sawtooth with horizontal edges leaves the sweep structure empty at some vertices
+        let contour: Vec<_> = [(0.0, 0.0), (1.0, 2.0), (2.0, 0.0), (3.0, 2.0), (4.0, 0.0), (4.0, 3.0), (0.0, 3.0)]
+            .iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+
+        // Either way, the triangles are counterclockwise, not degenerate, and cover the polygon
+        for contour in &[contour.clone(), contour.iter().rev().copied().collect()] {
+            let triangles = triangulate(std::iter::once(contour.clone()));
+            for t in &triangles { assert!((t.b - t.a).cross(t.c - t.a) > EPSILON, "{:?}", t); }
+            let covered: Coord = triangles.iter().map(|t| (t.b - t.a).cross(t.c - t.a)).sum();
+            assert!(covered.roughly_equals(polygon_winding(contour).abs()), "{} {}", covered, polygon_winding(contour));
+        }
+    }
+
+    #[test]
+    fn test_triangulate_iter() {
+        let contour: Vec<_> = [(0.0, 0.0), (4.0, 1.0), (2.0, 2.5), (4.0, 4.0), (0.5, 3.0)]
+            .iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+        let area = polygon_winding(&contour);
+
+        // The streamed triangles cover the polygon, and can be consumed partially
+        let triangles: Vec<_> = triangulate_iter(std::iter::once(contour.clone())).collect();
+        let covered: Coord = triangles.iter().map(|t| (t.b - t.a).cross(t.c - t.a).abs()).sum();
+        assert!((covered - area).abs() < 1e-9);
+        assert_eq!(triangles.len(), triangulate(std::iter::once(contour.clone())).len());
+        assert_eq!(triangulate_iter(std::iter::once(contour)).take(1).count(), 1);
+    }
+
+    #[test]
+    fn test_triangulate_presimplified() {
+        let contour: Vec<_> = [(0.0, 0.0), (4.0, 1.0), (2.0, 2.5), (4.0, 4.0), (0.5, 3.0)]
+            .iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+
+        // Every triangle vertex is one of the contour's vertices
+        let triangles = triangulate_presimplified(std::iter::once(contour.clone()));
+        assert_eq!(triangles.len(), contour.len() - 2);
+        for t in &triangles {
+            assert!([t.a, t.b, t.c].iter().all(|v| contour.contains(v)));
+        }
+    }
+
+    #[test]
+    fn test_triangulate_indexed() {
+        let outer: Vec<_> = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)].iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+        let hole: Vec<_> = [(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)].iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+        let contours = vec![outer, hole];
+
+        // The vertices are unique, and the index triples give back the same triangles as triangulate
+        let (vertices, indices) = triangulate_indexed(contours.clone().into_iter());
+        assert_eq!(vertices.len(), 8);
+        for (i, v) in vertices.iter().enumerate() { assert!(!vertices[i+1..].contains(v)); }
+        assert!(indices.iter().flatten().all(|&i| (i as usize) < vertices.len()));
+
+        let triangles = triangulate(contours.into_iter());
+        assert_eq!(indices.len(), triangles.len());
+        for (&[a, b, c], t) in indices.iter().zip(&triangles) {
+            assert!(vertices[a as usize] == t.a && vertices[b as usize] == t.b && vertices[c as usize] == t.c);
+        }
+    }
+
+    #[test]
+    fn test_triangulate_with_edge_flags() {
+        // A square with a square hole, so that every triangle needs some diagonal
+        let outer: Vec<_> = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)].iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+        let hole: Vec<_> = [(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)].iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+        let contours = vec![outer, hole];
+
+        let tagged = triangulate_with_edge_flags(contours.clone().into_iter());
+        assert_eq!(tagged.len(), triangulate(contours.into_iter()).len());
+
+        // The flagged edges are exactly the eight sides, the other ones being shared by two triangles
+        let flagged = tagged.iter().map(|(_, f)| f.iter().filter(|&&b| b).count()).sum::<usize>();
+        assert_eq!(flagged, 8);
+        for (t, flags) in &tagged {
+            let edges = [(t.a, t.b), (t.b, t.c), (t.c, t.a)];
+            for (&(v1, v2), &flag) in edges.iter().zip(flags.iter()) {
+                let axis_aligned = v1.x == v2.x || v1.y == v2.y;
+                let on_side = axis_aligned && [0.0, 1.0, 3.0, 4.0].iter().any(|&k| (v1.x == k && v2.x == k) || (v1.y == k && v2.y == k));
+                assert_eq!(flag, on_side, "{} -> {}", v1, v2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_triangulate_with_holes() {
+        let points = |pts: &[(Coord, Coord)]| -> Vec<_> { pts.iter().map(|&(x, y)| Vec2::new(x, y)).collect() };
+        let outer = points(&[(0.0, 0.0), (6.0, 0.0), (6.0, 4.0), (0.0, 4.0)]);
+        let hole1 = points(&[(1.0, 1.0), (2.0, 1.0), (2.0, 3.0), (1.0, 3.0)]);
+        let hole2 = points(&[(4.0, 1.0), (5.0, 3.0), (4.0, 3.0)]);
+
+        // The holes come counterclockwise, like the outer contour, and every contour after the first
+        // one has its vertices offset in the shared arrays
+        let triangles = triangulate_with_holes(&outer, &[hole1.clone(), hole2.clone()]);
+        for t in &triangles { assert!((t.b - t.a).cross(t.c - t.a) > 0.0, "{:?}", t); }
+        let covered: Coord = triangles.iter().map(|t| (t.b - t.a).cross(t.c - t.a)).sum();
+        let expected = polygon_winding(&outer) - polygon_winding(&hole1) - polygon_winding(&hole2);
+        assert!(covered.roughly_equals(expected), "{} {}", covered, expected);
+    }
+
+    #[test]
+    fn test_either_orientation() {
+        let points = |pts: &[(Coord, Coord)]| -> Vec<_> { pts.iter().map(|&(x, y)| Vec2::new(x, y)).collect() };
+        let reversed = |c: &Vec<Vec2>| -> Vec<_> { c.iter().rev().copied().collect() };
+        let outer = points(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]);
+        let hole = points(&[(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)]);
+        let island = points(&[(1.5, 1.5), (2.5, 1.5), (2.5, 2.5), (1.5, 2.5)]);
+
+        // How many times each sample point is covered, which must be the same in every orientation
+        let coverage = |contours: Vec<Vec<Vec2>>| -> Vec<usize> {
+            let triangles = triangulate(contours.into_iter());
+            for t in &triangles { assert!((t.b - t.a).cross(t.c - t.a) > 0.0); }
+            let inside = |t: &Triangle, p: Vec2| (t.b - t.a).cross(p - t.a) > 0.0
+                && (t.c - t.b).cross(p - t.b) > 0.0 && (t.a - t.c).cross(p - t.c) > 0.0;
+            (0..100).map(|k| Vec2::new(0.2 + (k % 10) as Coord * 0.4 + 0.01, 0.2 + (k / 10) as Coord * 0.4 + 0.02))
+                .map(|p| triangles.iter().filter(|t| inside(t, p)).count()).collect()
+        };
+
+        let square = coverage(vec![outer.clone()]);
+        assert!(square.iter().all(|&n| n == 1));
+        assert_eq!(coverage(vec![reversed(&outer)]), square);
+
+        let ring = coverage(vec![outer.clone(), hole.clone(), island.clone()]);
+        assert_eq!(ring.iter().sum::<usize>(), 100 - 25 + 4);
+        assert_eq!(coverage(vec![reversed(&outer), reversed(&hole), reversed(&island)]), ring);
+        assert_eq!(coverage(vec![reversed(&island), outer, reversed(&hole)]), ring);
+    }
+
+    #[test]
+    fn test_triangulate_oriented() {
+        let points = |pts: &[(Coord, Coord)]| -> Vec<_> { pts.iter().map(|&(x, y)| Vec2::new(x, y)).collect() };
+        let outer = points(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]);
+        let hole = points(&[(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)]);
+
+        // Already oriented contours give the same triangles without the nesting pass
+        let oriented = triangulate_oriented(vec![outer.clone(), hole.clone()].into_iter());
+        let nested = triangulate(vec![hole.iter().rev().copied().collect(), outer].into_iter());
+        let area = |ts: &[Triangle]| ts.iter().map(|t| (t.b - t.a).cross(t.c - t.a)).sum::<Coord>();
+        assert_eq!(oriented.len(), nested.len());
+        assert!(area(&oriented).roughly_equals(24.0) && area(&nested).roughly_equals(24.0));
+    }
+
+    #[test]
+    fn test_ear_clipping_fallback() {
+        let square: Vec<_> = [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)].iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+        let mut triangles = Vec::new();
+        triangulate_ear_clipping(&mut triangles, &square);
+        assert_eq!(triangles.len(), 2);
+        assert!(covers_polygon(&triangles, &square));
+
+        // Unlike a fan from the first vertex, it doesn't spill out of the non-convex polygons
+        let arrow: Vec<_> = [(0.0, 0.0), (2.0, 1.0), (4.0, 0.0), (2.0, 3.0)].iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+        let mut arrow_triangles = Vec::new();
+        triangulate_ear_clipping(&mut arrow_triangles, &arrow);
+        assert_eq!(arrow_triangles.len(), 2);
+        assert!(covers_polygon(&arrow_triangles, &arrow));
+        assert!(arrow_triangles.iter().all(|&[i, j, k]| (arrow[j] - arrow[i]).cross(arrow[k] - arrow[i]) > 0.0));
+
+        // Neither a missing triangle nor an overlapping one gets through
+        assert!(!covers_polygon(&triangles[..1], &square));
+        triangles.push([0, 2, 1]);
+        assert!(!covers_polygon(&triangles, &square));
+    }
+
+    #[test]
+    fn test_collinear_left_chain() {
+        // Several collinear vertices on the left chain, plus some sharing their y with the right chain
+        let contours = [
+            vec![(0.0, 0.0), (3.0, 0.0), (3.0, 4.0), (0.0, 4.0), (0.0, 3.0), (0.0, 2.0), (0.0, 1.0)],
+            vec![(0.0, 0.0), (3.0, 0.0), (3.0, 1.0), (3.0, 2.0), (3.0, 3.0), (3.0, 4.0), (0.0, 4.0), (0.0, 3.0), (0.0, 2.0), (0.0, 1.0)],
+            vec![(0.0, 0.0), (3.0, 1.0), (3.0, 2.0), (3.0, 3.0), (0.0, 4.0), (1.0, 3.0), (1.0, 2.0), (1.0, 1.0)],
+        ];
+
+        for contour in contours.iter() {
+            let contour: Vec<_> = contour.iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+
+            // The monotone triangulation alone covers every piece, without the fallback
+            for (piece, _) in partition_to_monotone(std::iter::once(contour.clone())) {
+                let mut triangles = Vec::new();
+                triangulate_monotone(&mut triangles, &piece);
+                assert!(covers_polygon(&triangles, &piece), "{:?}", triangles);
+            }
+
+            let triangles = triangulate_presimplified(std::iter::once(contour.clone()));
+            for t in &triangles { assert!((t.b - t.a).cross(t.c - t.a) > 0.0, "{:?}", triangles); }
+            let covered: Coord = triangles.iter().map(|t| (t.b - t.a).cross(t.c - t.a)).sum();
+            assert!((covered - polygon_winding(&contour)).abs() < 1e-9, "{:?}", triangles);
+        }
+    }
+}
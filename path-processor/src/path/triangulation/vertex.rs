@@ -5,23 +5,20 @@
 //------------------------------------------------------------------------------
 
 use crate::geometry::*;
-use std::collections::BTreeMap;
 use std::cmp::Ordering;
-use std::ops::Bound::*;
-use super::edge::EdgeKey;
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
 pub enum VertexType { End, Start, Split, RegularLeft, RegularRight, Merge }
 
 pub struct DcelVertex {
     pub type_: VertexType, pub cur: Vec2,
-    pub outgoing: BTreeMap<EdgeKey, usize>, pub incoming: BTreeMap<EdgeKey, usize>,
+    pub outgoing: Vec<usize>,
     pub next_edge: usize, pub prev_edge: usize
 }
 
 #[derive(Copy, Clone)]
 pub struct ChainVertex {
-    pub pos: Vec2, pub type_: VertexType
+    pub pos: Vec2, pub index: usize, pub type_: VertexType
 }
 
 impl DcelVertex {
@@ -42,33 +39,7 @@ impl DcelVertex {
             (_, _) => VertexType::RegularRight
         };
 
-        DcelVertex { type_, cur, outgoing: BTreeMap::new(), incoming: BTreeMap::new(), next_edge: 0, prev_edge: 0 }
-    }
-
-    pub fn search_outgoing(&self, key: EdgeKey) -> Option<(usize, usize)> {
-        if self.outgoing.is_empty() || self.outgoing.contains_key(&key) { None }
-        else {
-            // Try to mimic a cyclical edge list
-            let mut before = self.outgoing.range((Unbounded, Excluded(key)));
-            let mut after = self.outgoing.range((Excluded(key), Unbounded));
-
-            let before = before.next_back().or(self.outgoing.iter().next_back()).unwrap();
-            let after = after.next().or(self.outgoing.iter().next()).unwrap();
-            Some((*before.1, *after.1))
-        }
-    }
-
-    pub fn search_incoming(&self, key: EdgeKey) -> Option<(usize, usize)> {
-        if self.incoming.is_empty() || self.incoming.contains_key(&key) { None }
-        else {
-            // Try to mimic a cyclical edge list
-            let mut before = self.incoming.range((Unbounded, Excluded(key)));
-            let mut after = self.incoming.range((Excluded(key), Unbounded));
-
-            let before = before.next_back().or(self.incoming.iter().next_back()).unwrap();
-            let after = after.next().or(self.incoming.iter().next()).unwrap();
-            Some((*before.1, *after.1))
-        }
+        DcelVertex { type_, cur, outgoing: Vec::new(), next_edge: 0, prev_edge: 0 }
     }
 }
 
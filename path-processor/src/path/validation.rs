@@ -0,0 +1,67 @@
+//------------------------------------------------------------------------------
+// validation.rs
+//------------------------------------------------------------------------------
+// Provides the validation of paths coming from untrusted sources, before they
+// reach the processing pipeline
+//------------------------------------------------------------------------------
+
+use crate::derive_more::*;
+use super::*;
+
+#[derive(Clone, Copy, Debug, Display)]
+#[display(fmt = "Command {} ({}) has a non-finite coordinate", index, command)]
+pub struct ValidationError {
+    pub index: usize,
+    pub command: PathCommand
+}
+
+impl std::error::Error for ValidationError {}
+
+#[derive(Clone, Debug, Display)]
+#[display(fmt = "Subpaths {:?} are not closed", subpaths)]
+pub struct OpenSubpathError {
+    pub subpaths: Vec<usize>
+}
+
+impl std::error::Error for OpenSubpathError {}
+
+fn is_command_finite(cmd: &PathCommand) -> bool {
+    match cmd {
+        PathCommand::MoveTo(target) | PathCommand::LineTo(target) => target.is_finite(),
+        PathCommand::QuadraticBezierTo(c, t) => c.is_finite() && t.is_finite(),
+        PathCommand::CubicBezierTo(c1, c2, t) => c1.is_finite() && c2.is_finite() && t.is_finite(),
+        PathCommand::EllipticArcTo(radii, angle, _, _, target) =>
+            radii.is_finite() && angle.is_finite() && target.is_finite(),
+        PathCommand::ClosePath => true
+    }
+}
+
+// Check that every coordinate in the path is finite, reporting the first offending command
+pub fn validate_path(path: &Path) -> std::result::Result<(), ValidationError> {
+    match path.iter().position(|cmd| !is_command_finite(cmd)) {
+        Some(index) => Err(ValidationError { index, command: path[index] }),
+        None => Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_path() {
+        let mut path = vec![
+            PathCommand::MoveTo(Vec2::new(0.0, 0.0)),
+            PathCommand::LineTo(Vec2::new(1.0, 0.0)),
+            PathCommand::EllipticArcTo(Vec2::new(1.0, 1.0), 0.0, false, true, Vec2::new(0.0, 1.0)),
+            PathCommand::ClosePath
+        ];
+        assert!(validate_path(&path).is_ok());
+
+        path[2] = PathCommand::EllipticArcTo(Vec2::new(1.0, 1.0), Coord::NAN, false, true, Vec2::new(0.0, 1.0));
+        assert_eq!(validate_path(&path).unwrap_err().index, 2);
+
+        path[1] = PathCommand::LineTo(Vec2::new(Coord::INFINITY, 0.0));
+        assert_eq!(validate_path(&path).unwrap_err().index, 1);
+    }
+}
\ No newline at end of file
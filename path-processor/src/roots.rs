@@ -0,0 +1,119 @@
+//--------------------------------------------------------------------
+// roots.rs
+//--------------------------------------------------------------------
+// Provides the closed-form solvers of the linear, quadratic and cubic
+// equations, standing in for the roots crate in the builds without std
+//--------------------------------------------------------------------
+
+use crate::geometry::*;
+use crate::arrayvec::ArrayVec;
+
+// The real roots of an equation, sorted and without repetitions; it has the same shape as the
+// roots crate's, so the curves use either one the same way
+#[derive(Debug, PartialEq)]
+pub enum Roots<F> { No([F; 0]), One([F; 1]), Two([F; 2]), Three([F; 3]), Four([F; 4]) }
+
+impl<F> AsRef<[F]> for Roots<F> {
+    fn as_ref(&self) -> &[F] {
+        match self {
+            Roots::No(x) => x,
+            Roots::One(x) => x,
+            Roots::Two(x) => x,
+            Roots::Three(x) => x,
+            Roots::Four(x) => x
+        }
+    }
+}
+
+impl<F: Copy + PartialOrd> Roots<F> {
+    // Add a root, keeping the roots sorted and unique
+    pub fn add_new_root(self, root: F) -> Roots<F> {
+        let mut roots: ArrayVec<[F; 4]> = self.as_ref().iter().copied().collect();
+        if roots.contains(&root) { return self; }
+
+        let pos = roots.iter().position(|&r| r > root).unwrap_or(roots.len());
+        roots.insert(pos, root);
+        match *roots.as_slice() {
+            [a] => Roots::One([a]),
+            [a, b] => Roots::Two([a, b]),
+            [a, b, c] => Roots::Three([a, b, c]),
+            [a, b, c, d] => Roots::Four([a, b, c, d]),
+            _ => unreachable!()
+        }
+    }
+}
+
+// Solve a1*x + a0 = 0; an identically zero equation has 0 as its root
+pub fn find_roots_linear(a1: Coord, a0: Coord) -> Roots<Coord> {
+    if a1 != 0.0 { Roots::One([-a0 / a1]) }
+    else if a0 == 0.0 { Roots::One([0.0]) }
+    else { Roots::No([]) }
+}
+
+// Solve a2*x^2 + a1*x + a0 = 0, taking the root whose numerator doesn't cancel out and getting the
+// other one from their product, for precision
+pub fn find_roots_quadratic(a2: Coord, a1: Coord, a0: Coord) -> Roots<Coord> {
+    if a2 == 0.0 { return find_roots_linear(a1, a0); }
+
+    let discriminant = a1 * a1 - 4.0 * a2 * a0;
+    if discriminant < 0.0 { Roots::No([]) }
+    else if discriminant == 0.0 { Roots::One([-a1 / (2.0 * a2)]) }
+    else {
+        let q = -0.5 * (a1 + a1.signum() * discriminant.sqrt());
+        Roots::One([q / a2]).add_new_root(a0 / q)
+    }
+}
+
+// Solve a3*x^3 + a2*x^2 + a1*x + a0 = 0 through the depressed cubic t^3 + p*t + q = 0, with
+// x = t - b/3: Cardano's formula when it has a single real root, the trigonometric one otherwise
+pub fn find_roots_cubic(a3: Coord, a2: Coord, a1: Coord, a0: Coord) -> Roots<Coord> {
+    if a3 == 0.0 { return find_roots_quadratic(a2, a1, a0); }
+
+    let (b, c, d) = (a2 / a3, a1 / a3, a0 / a3);
+    let p = c - b * b / 3.0;
+    let q = 2.0 * b * b * b / 27.0 - b * c / 3.0 + d;
+    let shift = -b / 3.0;
+
+    let discriminant = q * q / 4.0 + p * p * p / 27.0;
+    if p == 0.0 && q == 0.0 { Roots::One([shift]) }
+    else if discriminant > 0.0 {
+        let sq = discriminant.sqrt();
+        Roots::One([(-q / 2.0 + sq).cbrt() + (-q / 2.0 - sq).cbrt() + shift])
+    }
+    else if discriminant == 0.0 {
+        // A simple root and a double one
+        Roots::One([3.0 * q / p + shift]).add_new_root(-1.5 * q / p + shift)
+    }
+    else {
+        let r = 2.0 * (-p / 3.0).sqrt();
+        let phi = (1.5 * q / p * (-3.0 / p).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+        (0..3).map(|k| r * (phi - TWO_PI * k as Coord / 3.0).cos() + shift)
+            .fold(Roots::No([]), |roots, x| roots.add_new_root(x))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_roots(roots: Roots<Coord>, expected: &[Coord]) {
+        assert_eq!(roots.as_ref().len(), expected.len(), "{:?}", roots);
+        for (r, e) in roots.as_ref().iter().zip(expected) { assert!(r.roughly_equals(*e), "{:?}", roots); }
+    }
+
+    #[test]
+    fn test_find_roots() {
+        assert_roots(find_roots_linear(2.0, 1.0), &[-0.5]);
+        assert_roots(find_roots_linear(0.0, 1.0), &[]);
+        assert_roots(find_roots_quadratic(1.0, 0.0, 1.0), &[]);
+        assert_roots(find_roots_quadratic(2.0, -4.0, 2.0), &[1.0]);
+        assert_roots(find_roots_quadratic(1.0, -1e8, 1.0), &[1e-8, 1e8]);
+
+        // Three simple roots, a simple and a double one, a triple one and a single real one
+        assert_roots(find_roots_cubic(2.0, -12.0, 22.0, -12.0), &[1.0, 2.0, 3.0]);
+        assert_roots(find_roots_cubic(1.0, 0.0, -3.0, 2.0), &[-2.0, 1.0]);
+        assert_roots(find_roots_cubic(1.0, -3.0, 3.0, -1.0), &[1.0]);
+        assert_roots(find_roots_cubic(1.0, 0.0, 1.0, -2.0), &[1.0]);
+        assert_roots(find_roots_cubic(0.0, 1.0, 0.0, -1.0), &[-1.0, 1.0]);
+    }
+}
@@ -0,0 +1,100 @@
+//--------------------------------------------------------------------
+// test_paths.rs
+//--------------------------------------------------------------------
+// Provides the sample paths bundled with the repository, so tests and
+// benchmarks can run the pipeline on known inputs
+//--------------------------------------------------------------------
+
+use crate::geometry::*;
+use crate::curve::*;
+use crate::path::*;
+
+const BIG_PATH: &str = include_str!("../../bigpath.txt");
+const MONOGAME_PATH: &str = include_str!("../../monogame.txt");
+
+// The big path with hundreds of cubic Béziers
+pub fn big_path() -> Path { parse_sample(BIG_PATH) }
+
+// The MonoGame logo
+pub fn monogame_path() -> Path { parse_sample(MONOGAME_PATH) }
+
+// The contour of lines around the rectangle, starting at (x, y) and going through (x + w, y)
+pub fn rectangle(x: Coord, y: Coord, w: Coord, h: Coord) -> Vec<Curve> {
+    let pts = Rect::new(x, y, w, h).vertices();
+    (0..4).map(|i| Curve::line(pts[i], pts[(i+1) % 4])).collect()
+}
+
+pub fn square(x: Coord, y: Coord, size: Coord) -> Vec<Curve> { rectangle(x, y, size, size) }
+
+// A minimal parser for the path data of the samples (the full one lives in path-svg-loader,
+// which depends on this crate); it only knows the commands they use: M, L, H, V, C and Z
+fn parse_sample(data: &str) -> Path {
+    let mut tokens = data.split(|c: char| c.is_whitespace() || c == ',').filter(|s| !s.is_empty()).peekable();
+    let mut path = Vec::new();
+    let mut cmd = 'M';
+    let mut first_vec = Vec2::zero();
+    let mut last_vec = Vec2::zero();
+
+    while let Some(&token) = tokens.peek() {
+        // Numbers after a command repeat it (and a repeated move becomes a line)
+        if token.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            cmd = token.chars().next().unwrap();
+            tokens.next();
+        } else if cmd == 'M' { cmd = 'L'; }
+        else if cmd == 'm' { cmd = 'l'; }
+
+        let mut num = || tokens.next().unwrap().parse::<Coord>().unwrap();
+        let base = if cmd.is_ascii_lowercase() { last_vec } else { Vec2::zero() };
+
+        match cmd.to_ascii_uppercase() {
+            'M' => {
+                last_vec = base + Vec2::new(num(), num());
+                first_vec = last_vec;
+                path.push(PathCommand::MoveTo(last_vec));
+            }
+            'L' => {
+                last_vec = base + Vec2::new(num(), num());
+                path.push(PathCommand::LineTo(last_vec));
+            }
+            'H' => {
+                last_vec = Vec2::new(base.x + num(), last_vec.y);
+                path.push(PathCommand::LineTo(last_vec));
+            }
+            'V' => {
+                last_vec = Vec2::new(last_vec.x, base.y + num());
+                path.push(PathCommand::LineTo(last_vec));
+            }
+            'C' => {
+                let ctl1 = base + Vec2::new(num(), num());
+                let ctl2 = base + Vec2::new(num(), num());
+                last_vec = base + Vec2::new(num(), num());
+                path.push(PathCommand::CubicBezierTo(ctl1, ctl2, last_vec));
+            }
+            'Z' => {
+                last_vec = first_vec;
+                path.push(PathCommand::ClosePath);
+            }
+            _ => panic!("Unsupported command {} in sample path", cmd)
+        }
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sample_paths() {
+        let path = big_path();
+        assert_eq!(path.iter().filter(|c| matches!(c, PathCommand::MoveTo(_))).count(), 33);
+        assert_eq!(path.iter().filter(|c| matches!(c, PathCommand::ClosePath)).count(), 32);
+
+        let path = monogame_path();
+        assert!(validate_path(&path).is_ok());
+        assert!(path_bbox(&path).is_some());
+        let drawing = compile_path(&path, FillRule::NonZero);
+        assert!(!drawing.triangles.is_empty());
+    }
+}
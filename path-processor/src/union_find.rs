@@ -35,4 +35,41 @@ impl UnionFind {
             self.sizes[i] += self.sizes[j];
         }
     }
+
+    #[cfg(test)]
+    pub fn connected(&mut self, i: usize, j: usize) -> bool { self.find(i) == self.find(j) }
+
+    // Group all the indices by their components, ordered by their smallest index
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut component_of_root = vec![usize::MAX; self.parents.len()];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+
+        for i in 0..self.parents.len() {
+            let root = self.find(i);
+            if component_of_root[root] == usize::MAX {
+                component_of_root[root] = components.len();
+                components.push(Vec::with_capacity(self.sizes[root]));
+            }
+            components[component_of_root[root]].push(i);
+        }
+
+        components
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_components() {
+        let mut uf = UnionFind::new(6);
+        uf.union(4, 1);
+        uf.union(2, 5);
+        uf.union(5, 4);
+
+        assert!(uf.connected(1, 2));
+        assert!(!uf.connected(0, 3));
+        assert_eq!(uf.components(), vec![vec![0], vec![1, 2, 4, 5], vec![3]]);
+    }
 }
\ No newline at end of file
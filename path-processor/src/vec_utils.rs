@@ -5,7 +5,11 @@
 //--------------------------------------------------------------------
 
 use arrayvec::*;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
+// Reorder the elements so the ones for which the predicate is false come first, returning where
+// the ones for which it is true begin; the order within each group is not kept
 pub fn partition_inplace_false_first<T>(elems: &mut [T], p: impl Fn(&T) -> bool) -> usize {
     let mut i = 0;
     while i < elems.len() {
@@ -23,10 +27,13 @@ pub fn partition_inplace_false_first<T>(elems: &mut [T], p: impl Fn(&T) -> bool)
     i
 }
 
+// Remove the elements at the given indices, keeping the order of the remaining ones; the indices
+// can come in any order and be repeated, but they all must be in range
 pub trait RemoveIndices<T> {
     fn remove_indices(&mut self, indices: Vec<usize>);
 }
 
+// Take out the elements for which the predicate holds, which are returned in no particular order
 pub trait ExtractAll<T> {
     fn extract_all(&mut self, p: impl Fn(&T) -> bool) -> Vec<T>;
 }
@@ -45,7 +52,7 @@ impl<T: Copy> RemoveIndices<T> for Vec<T> {
         let mut k = 1;
 
         for i in ik+1..len {
-            if i == indices[k] { k += 1; }
+            if k < indices.len() && i == indices[k] { k += 1; }
             else { 
                 self[ik] = self[i];
                 ik += 1;
@@ -63,6 +70,7 @@ impl<T> ExtractAll<T> for Vec<T> {
     }
 }
 
+// Remove the consecutive repeated elements; unlike Vec::dedup_by, the last element of each run is kept
 pub fn arrayvec_dedup_by<A: Array>(v: &mut ArrayVec<A>, f: impl Fn(&A::Item, &A::Item) -> bool)
     where A::Item: Copy + PartialEq {
     if v.len() < 2 { return; }
@@ -80,4 +88,51 @@ pub fn arrayvec_dedup_by<A: Array>(v: &mut ArrayVec<A>, f: impl Fn(&A::Item, &A:
 pub fn arrayvec_dedup<A: Array>(v: &mut ArrayVec<A>)
     where A::Item: Copy + PartialEq {
     arrayvec_dedup_by(v, |&a, &b| a == b);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[test]
+    fn test_remove_indices() {
+        // The indices are sorted and deduplicated first, and the remaining order is kept
+        let mut v: Vec<_> = (0..6).collect();
+        v.remove_indices(vec![3, 1, 3]);
+        assert_eq!(v, vec![0, 2, 4, 5]);
+
+        let mut v: Vec<_> = (0..6).collect();
+        v.remove_indices(vec![5, 0]);
+        assert_eq!(v, vec![1, 2, 3, 4]);
+
+        let mut v: Vec<_> = (0..3).collect();
+        v.remove_indices(Vec::new());
+        assert_eq!(v, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_partition_and_extract() {
+        let mut v = vec![1, 4, 2, 7, 6, 3];
+        let idx = partition_inplace_false_first(&mut v, |&x| x % 2 == 0);
+        assert_eq!(idx, 3);
+        assert!(v[..idx].iter().all(|x| x % 2 == 1) && v[idx..].iter().all(|x| x % 2 == 0));
+
+        let mut evens = v.extract_all(|&x| x > 3);
+        evens.sort();
+        assert_eq!(evens, vec![4, 6, 7]);
+        assert_eq!(v.len(), 3);
+    }
+
+    #[test]
+    fn test_arrayvec_dedup() {
+        let mut v: ArrayVec<[i32; 8]> = [1, 1, 2, 3, 3, 3, 1].iter().copied().collect();
+        arrayvec_dedup(&mut v);
+        assert_eq!(v.as_slice(), &[1, 2, 3, 1]);
+
+        let mut v: ArrayVec<[f64; 4]> = [0.0, 0.01, 0.5, 1.0].iter().copied().collect();
+        arrayvec_dedup_by(&mut v, |a, b| (a - b).abs() < 0.1);
+        assert_eq!(v.as_slice(), &[0.01, 0.5, 1.0]);
+    }
+}
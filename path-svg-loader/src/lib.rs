@@ -9,6 +9,9 @@ extern crate svg;
 
 use path_processor::*;
 use svg::node::element::path::*;
+use svg::node::element::tag;
+use svg::node::Attributes;
+use svg::parser::{Event, Parser};
 
 fn process_relative(cmd: Vec2, relative: Position, last_value: Vec2) -> Vec2 {
     match relative {
@@ -121,7 +124,9 @@ pub fn path_from_string(data: &str) -> svg::parser::Result<Path> {
                     let target = process_update_relative(Vec2::new(cmd[5] as Coord, cmd[6] as Coord), *pos, &mut last_value);
                     last_command = LastCmd::Other;
                     last_control = target;
-                    path.push(PathCommand::EllipticArcTo(radii, rangle, large_arc, sweep, target));
+                    // The spec treats arcs with a zero radius as straight lines
+                    if radii.x == 0.0 || radii.y == 0.0 { path.push(PathCommand::LineTo(target)); }
+                    else { path.push(PathCommand::EllipticArcTo(radii, rangle, large_arc, sweep, target)); }
                 }
             }
             Command::Close => {
@@ -133,3 +138,106 @@ pub fn path_from_string(data: &str) -> svg::parser::Result<Path> {
 
     Ok(path)
 }
+
+// Same as path_from_string, but scaling the coordinates by scale and then translating them by
+// translate, e.g. to map a viewBox onto the viewport
+pub fn path_from_string_scaled(data: &str, scale: Vec2, translate: Vec2) -> svg::parser::Result<Path> {
+    Ok(scale_path(&path_from_string(data)?, scale, translate))
+}
+
+// The fill rule an element sets, either by its attribute or by its inline style
+fn element_fill_rule(attributes: &Attributes) -> Option<FillRule> {
+    let from_style = attributes.get("style").and_then(|style| style.split(';').rev()
+        .find_map(|decl| {
+            let mut parts = decl.splitn(2, ':');
+            match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) if name.trim() == "fill-rule" => Some(value.trim()),
+                _ => None
+            }
+        }));
+
+    // The inline style takes precedence over the presentation attribute
+    match from_style.or_else(|| attributes.get("fill-rule").map(|v| v.trim())) {
+        Some("evenodd") => Some(FillRule::EvenOdd),
+        Some("nonzero") => Some(FillRule::NonZero),
+        _ => None
+    }
+}
+
+// Extract all the paths in an SVG document, along with their fill rules; the fill rule
+// is inherited from the enclosing elements and defaults to nonzero, as in the SVG spec
+pub fn paths_from_svg(source: &str) -> svg::parser::Result<Vec<(Path, FillRule)>> {
+    let mut paths = Vec::new();
+    let mut fill_rules = vec![FillRule::NonZero];
+
+    for event in Parser::new(source) {
+        match event {
+            Event::Error(error) => return Err(error),
+            Event::Tag(name, kind, attributes) => {
+                let parent = *fill_rules.last().unwrap();
+                let fill_rule = element_fill_rule(&attributes).unwrap_or(parent);
+
+                match kind {
+                    tag::Type::Start => fill_rules.push(fill_rule),
+                    tag::Type::End => if fill_rules.len() > 1 { fill_rules.pop(); },
+                    tag::Type::Empty => {}
+                }
+
+                if name == tag::Path && kind != tag::Type::End {
+                    if let Some(data) = attributes.get("d") {
+                        paths.push((path_from_string(data)?, fill_rule));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_paths_from_svg() {
+        let source = r#"<svg xmlns="http://www.w3.org/2000/svg">
+            <path d="M0,0 L1,0 L1,1 Z"/>
+            <g fill-rule="evenodd">
+                <path d="M0,0 L2,0 L2,2 Z"/>
+                <path d="M0,0 L3,0 L3,3 Z" style="fill: red; fill-rule: nonzero"/>
+            </g>
+            <path d="M0,0 L4,0 L4,4 Z" fill-rule="evenodd"/>
+        </svg>"#;
+
+        let paths = paths_from_svg(source).unwrap();
+        let fill_rules: Vec<_> = paths.iter().map(|p| p.1).collect();
+        assert_eq!(fill_rules, vec![FillRule::NonZero, FillRule::EvenOdd, FillRule::NonZero, FillRule::EvenOdd]);
+        assert!(paths.iter().all(|p| p.0.len() == 4));
+
+        assert!(paths_from_svg(r#"<svg><path d="M0,0 L1,x"/></svg>"#).is_err());
+    }
+
+    #[test]
+    fn test_zero_radius_arc() {
+        let path = path_from_string("M0,0 A0 0 0 0 0 10 10 a5 0 0 0 0 -5 0 A1 1 0 0 0 6 10").unwrap();
+        assert_eq!(path.len(), 4);
+        match path[1] { PathCommand::LineTo(p) => assert!(p.roughly_equals(Vec2::new(10.0, 10.0))), _ => panic!() }
+        match path[2] { PathCommand::LineTo(p) => assert!(p.roughly_equals(Vec2::new(5.0, 10.0))), _ => panic!() }
+        match path[3] { PathCommand::EllipticArcTo(..) => {}, _ => panic!() }
+    }
+
+    #[test]
+    fn test_path_from_string_scaled() {
+        let path = path_from_string_scaled("M1,1 L2,1 A1 1 0 0 1 2 3 z", Vec2::new(2.0, 0.5), Vec2::new(1.0, 0.0)).unwrap();
+        match path[1] { PathCommand::LineTo(p) => assert!(p.roughly_equals(Vec2::new(5.0, 0.5))), _ => panic!() }
+        match path[2] {
+            PathCommand::EllipticArcTo(radii, _, _, _, p) => {
+                assert!(p.roughly_equals(Vec2::new(5.0, 1.5)));
+                assert!(radii.roughly_equals(Vec2::new(2.0, 0.5)));
+            }
+            _ => panic!()
+        }
+    }
+}